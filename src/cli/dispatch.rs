@@ -1,18 +1,23 @@
 // src/cli/dispatch.rs
 
+use std::path::PathBuf;
+
 use thiserror::Error;
 
 use crate::utils::output::{logo, help};
-use crate::commands::{create_cmd, new_cmd, build_cmd, server_cmd};
-use crate::commands::{create_cmd::CreateError, new_cmd::NewError, build_cmd::BuildError, server_cmd::ServerError};
+use crate::commands::{create_cmd, new_cmd, build_cmd, server_cmd, theme_cmd};
+use crate::commands::{create_cmd::CreateError, new_cmd::NewError, build_cmd::BuildError, server_cmd::ServerError, theme_cmd::ThemeCmdError};
 use crate::commands::new_cmd::NewKind;
+use crate::commands::server_cmd::ServerOptions;
+use crate::commands::theme_cmd::{ThemeAction, InstallOptions};
 
 #[derive(Debug)]
 pub enum Command {
     Create { root: String },
     New { title: String, kind: NewKind, root: String },
     Build { root: String },
-    Server { root: String, port: u16 },
+    Server { root: String, options: ServerOptions },
+    Theme { action: ThemeAction, root: String },
     Help,
 }
 
@@ -32,6 +37,9 @@ pub enum CliError {
 
     #[error(transparent)]
     ServerError(#[from] ServerError),
+
+    #[error(transparent)]
+    ThemeCmdError(#[from] ThemeCmdError),
 }
 
 #[derive(Error, Debug)]
@@ -80,9 +88,13 @@ fn dispatch(cmd: Command) -> Result<(), CliError> {
             // Implementation for building the blog into static files
             build_cmd::run(&root)?;
         }
-        Command::Server { port, root } => {
+        Command::Server { root, options } => {
             // Implementation for starting the local HTTP server
-            server_cmd::run(port, &root)?;
+            server_cmd::run(&root, options)?;
+        }
+        Command::Theme { action, root } => {
+            // Theme scaffolding, listing and validation
+            theme_cmd::run(action, &root)?;
         }
     }
     Ok(())
@@ -99,6 +111,7 @@ fn parse(argv: Vec<String>) -> Result<Command, ParseError> {
         "new" => parse_new(&argv[2..]),
         "build" => parse_build(&argv[2..]),
         "server" => parse_server(&argv[2..]),
+        "theme" => parse_theme(&argv[2..]),
         "help" => Ok(Command::Help),
         other => Err(ParseError::UnknownCommand(other.into())),
     }
@@ -196,7 +209,7 @@ fn parse_build(args: &[String]) -> Result<Command, ParseError> {
 
 fn parse_server(args: &[String]) -> Result<Command, ParseError> {
     let mut root = ".".to_string();
-    let mut port: u16 = 8000;
+    let mut options = ServerOptions::default();
 
     let mut i = 0;
     while i < args.len() {
@@ -212,15 +225,122 @@ fn parse_server(args: &[String]) -> Result<Command, ParseError> {
             "--port" => {
                 i += 1;
                 if i < args.len() {
-                    port = args[i].parse().map_err(|_| ParseError::InvalidOption("Invalid port number".into()))?;
+                    options.port = Some(args[i].parse().map_err(|_| ParseError::InvalidOption("Invalid port number".into()))?);
                 } else {
                     return Err(ParseError::MissingArgument("Expected value after --port".into()));
                 }
             }
+            "--host" => {
+                i += 1;
+                if i < args.len() {
+                    options.host = Some(args[i].clone());
+                } else {
+                    return Err(ParseError::MissingArgument("Expected value after --host".into()));
+                }
+            }
+            "--workers" => {
+                i += 1;
+                if i < args.len() {
+                    let workers: usize = args[i].parse().map_err(|_| ParseError::InvalidOption("Invalid worker count".into()))?;
+                    if workers == 0 {
+                        return Err(ParseError::InvalidOption("Worker count must be at least 1".into()));
+                    }
+                    options.workers = Some(workers);
+                } else {
+                    return Err(ParseError::MissingArgument("Expected value after --workers".into()));
+                }
+            }
+            "--access-log" => {
+                i += 1;
+                if i < args.len() {
+                    options.access_log = Some(args[i].clone());
+                } else {
+                    return Err(ParseError::MissingArgument("Expected value after --access-log".into()));
+                }
+            }
+            "--memory" => {
+                options.memory = true;
+            }
+            "--drafts" => {
+                options.drafts = true;
+            }
             other => return Err(ParseError::InvalidOption(format!("Unknown option for 'server': {}", other))),
         }
         i += 1;
     }
 
-    Ok(Command::Server { port, root })
-}
\ No newline at end of file
+    if options.drafts && !options.memory {
+        return Err(ParseError::InvalidOption("--drafts requires --memory".into()));
+    }
+
+    Ok(Command::Server { root, options })
+}
+
+fn parse_theme(args: &[String]) -> Result<Command, ParseError> {
+    if args.is_empty() {
+        return Err(ParseError::MissingArgument("Subcommand is required for 'theme' command (new, list, check, install)".into()));
+    }
+
+    let subcommand = args[0].to_lowercase();
+    let mut name: Option<String> = None;
+    let mut root = ".".to_string();
+    let mut install_options = InstallOptions::default();
+    let is_install = subcommand == "install";
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].to_lowercase().as_str() {
+            "--root" => {
+                i += 1;
+                if i < args.len() {
+                    root = args[i].clone();
+                } else {
+                    return Err(ParseError::MissingArgument("Expected value after --root".into()));
+                }
+            }
+            "--name" if is_install => {
+                i += 1;
+                if i < args.len() {
+                    install_options.name = Some(args[i].clone());
+                } else {
+                    return Err(ParseError::MissingArgument("Expected value after --name".into()));
+                }
+            }
+            "--force" if is_install => {
+                install_options.force = true;
+            }
+            "--use" if is_install => {
+                install_options.use_theme = true;
+            }
+            other if other.starts_with("--") => {
+                return Err(ParseError::InvalidOption(format!("Unknown option for 'theme {}': {}", subcommand, other)));
+            }
+            _ if name.is_none() => {
+                name = Some(args[i].clone());
+            }
+            _ => return Err(ParseError::InvalidOption(format!("Unexpected argument for 'theme {}': {}", subcommand, args[i]))),
+        }
+        i += 1;
+    }
+
+    let action = match subcommand.as_str() {
+        "new" => ThemeAction::New {
+            name: name.ok_or_else(|| ParseError::MissingArgument("Theme name is required for 'theme new'".into()))?,
+        },
+        "list" => {
+            if let Some(name) = name {
+                return Err(ParseError::InvalidOption(format!("Unexpected argument for 'theme list': {}", name)));
+            }
+            ThemeAction::List
+        }
+        "check" => ThemeAction::Check { name },
+        "install" => ThemeAction::Install {
+            source: name.map(PathBuf::from)
+                .ok_or_else(|| ParseError::MissingArgument("Theme directory or archive is required for 'theme install'".into()))?,
+            options: install_options,
+        },
+        other => return Err(ParseError::InvalidOption(format!("Unknown subcommand for 'theme': {}", other))),
+    };
+
+    Ok(Command::Theme { action, root })
+}
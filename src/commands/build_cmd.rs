@@ -1,21 +1,35 @@
 // src/commands/build.rs
 
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use thiserror::Error;
-use minijinja::{Environment, context};
+use minijinja::{Environment, UndefinedBehavior, context};
 
 use crate::config::blogconfig::{BlogConfig, BlogConfigError};
 use crate::config::theme::{ThemePackage, ThemeError, ArchiveKind};
+use crate::config::i18n::{UiStrings, I18nError};
 use crate::context::context_builder;
 use crate::content::content_source::{ContentKind, ContentSource};
-use crate::content::content_loader::{load_all_contents, ContentLoaderError};
+use crate::content::content_loader::{load_all_contents, ContentLoadOptions, ContentLoaderError};
+use crate::content::data_loader::{load_data_dir, DataLoaderError};
+use crate::content::git_history::load_last_commits;
 use crate::context::common_context::SiteContext;
 use crate::context::content_context::ContentContext;
 use crate::context::list_context::{ListKind, PostListItem};
+use crate::utils::assets::{self, AssetData, AssetError, ThemeAsset};
+use crate::utils::csp::CspSink;
 use crate::utils::filters;
+use crate::utils::functions::{self, SiteQueries};
+use crate::utils::images::{self, ImageError, ImagePipeline};
+use crate::utils::minify::MinifySink;
 use crate::utils::output;
+use crate::utils::output_sink::{OutputSink, DiskSink};
+use crate::utils::text::escape_xml;
+use crate::utils::url_helpers;
 
 
 #[derive(Error, Debug)]
@@ -29,8 +43,14 @@ pub enum BuildError {
     #[error("Content directory not found\n  Path: {path}\n  Expected: content/, content/posts/, or content/pages/")]
     ContentDirNotFound{ path: PathBuf },
 
-    #[error("Template not found\n  Template: {template}")]
-    TemplateNotFound { template: String },
+    #[error("Template not found\n  Template: {template}\n  Reason: {reason}")]
+    TemplateNotFound { template: String, reason: String },
+
+    #[error("Template error\n  Template: {template}\n  Rendering: {rendering}\n  Reason: {reason}{snippet}")]
+    TemplateError { template: String, rendering: String, reason: String, snippet: String },
+
+    #[error("Invalid content template\n  Path: {path}\n  Reason: {reason}")]
+    ContentTemplateError { path: PathBuf, reason: String },
 
     #[error("Data conversion error\n  Details: {0}")]
     ConvertError(String),
@@ -43,6 +63,18 @@ pub enum BuildError {
 
     #[error(transparent)]
     ContentLoaderError(#[from] ContentLoaderError),
+
+    #[error(transparent)]
+    I18nError(#[from] I18nError),
+
+    #[error(transparent)]
+    DataLoaderError(#[from] DataLoaderError),
+
+    #[error(transparent)]
+    AssetError(#[from] AssetError),
+
+    #[error(transparent)]
+    ImageError(#[from] ImageError),
 }
 
 pub fn run(root: &str) -> Result<(), BuildError> {
@@ -57,109 +89,466 @@ pub fn run(root: &str) -> Result<(), BuildError> {
     let blog_config = BlogConfig::load_from_file(&config_path)
         .map_err(|e| BuildError::BlogConfigError(e))?;
 
-    // Load theme data
-    let theme_package = ThemePackage::load_from_dir(&project_path.join("themes")
-        .join(&blog_config.theme.name))
-        .map_err(|e| BuildError::ThemeError(e))?;
+    // output directory
+    let output_dir = project_path.join(&blog_config.build.output_dir);
+    let mut sink = DiskSink::new(&output_dir, blog_config.build.clean);
 
-    // Load templates
-    let mut template_env = Environment::new();
-    template_env.set_loader(minijinja::path_loader(&theme_package.templates_dir));
-    filters::register_all(&mut template_env);
+    build_site(&project_path, &blog_config, &mut sink)?;
 
-    output::info(&format!("Building blog '{}' with theme '{}'", blog_config.site.name, theme_package.name));
+    //------------------------------------------------------------------------------
+    eprintln!();
+    output::success("Build completed successfully");
 
-    // output directory
-    let output_dir = project_path.join(&blog_config.build.output_dir);
-    let output_dir_index = output_dir.join("index.html");
+    Ok(())
+}
 
-    // Clean output directory if needed
-    if blog_config.build.clean && output_dir.is_dir() && output_dir_index.is_file(){
-        output::step("Cleaning output directory...");
-        fs::remove_dir_all(&output_dir)
-            .map_err(|e| BuildError::Io { path: output_dir.clone(), source: e })?;
+/// Runs the build pipeline, writing every generated file through `sink`.
+///
+/// Used by the `build` command (output directory) and `server --memory` (in-memory site).
+pub fn build_site(project_path: &Path, blog_config: &BlogConfig, sink: &mut dyn OutputSink) -> Result<(), BuildError> {
+    // Load theme data
+    let mut theme_package = ThemePackage::load_from_dir(&project_path.join("themes")
+        .join(&blog_config.theme.name))
+        .map_err(|e| BuildError::ThemeError(e))?;
+    theme_package.check_generator_version()?;
+    theme_package.apply_site_overrides(&blog_config.theme)?;
+
+    // Theme assets (Sass compiled to CSS), before templates so `asset_url` can fingerprint them
+    let mut theme_assets = assets::collect_theme_assets(&theme_package)?;
+    if blog_config.build.minify {
+        let minified = assets::minify_assets(&mut theme_assets)?;
+        output::info(&format!("{} asset(s) minified", minified));
     }
+    let asset_manifest = if blog_config.build.fingerprint {
+        Some(assets::fingerprint_assets(&mut theme_assets)?)
+    } else {
+        None
+    };
 
-    // Create output directory if it doesn't exist
-    if !output_dir.is_dir() {
-        output::step("Creating output directory...");
-        fs::create_dir_all(&output_dir)
-            .map_err(|e| BuildError::Io { path: output_dir.clone(), source: e })?;
-    }
-    output::info("Output directory:");
-    output::print_path(&output_dir.display().to_string());
+    // Load templates
+    let mut template_env = create_template_env(blog_config, &theme_package, &theme_assets);
+
+    let parent_label = match &theme_package.manifest.extends {
+        Some(parent) => format!(" (extends '{}')", parent),
+        None => String::new(),
+    };
+    output::info(&format!("Building blog '{}' with theme '{}'{}", blog_config.site.name, theme_package.name, parent_label));
+
+    // Generated HTML is scanned for the Content-Security-Policy after minification
+    let mut csp_sink = None;
+    let sink: &mut dyn OutputSink = if blog_config.build.headers {
+        csp_sink.insert(CspSink::new(sink, &blog_config.site.base_url))
+    } else {
+        sink
+    };
+
+    // Generated HTML is minified on its way to the output
+    let mut minify_sink;
+    let sink: &mut dyn OutputSink = if blog_config.build.minify {
+        minify_sink = MinifySink::new(sink);
+        &mut minify_sink
+    } else {
+        sink
+    };
+
+    // Prepare output (clean/create the output directory for disk builds)
+    sink.prepare()
+        .map_err(|e| BuildError::Io { path: sink.location(""), source: e })?;
 
     // Check content directories
     let content_dir = project_path.join("content");
     let content_post_dir = content_dir.join("posts");
     let content_page_dir = content_dir.join("pages");    
     if !content_dir.is_dir() || !content_post_dir.is_dir() || !content_page_dir.is_dir() {
-        return Err(BuildError::ContentDirNotFound { path: project_path.clone() });
+        return Err(BuildError::ContentDirNotFound { path: project_path.to_path_buf() });
     }
 
     //------------------------------------------------------------------------------
     // Load all contents
     //------------------------------------------------------------------------------
-    let all_contents = load_all_contents(&content_dir)
+    let language_codes = blog_config.language_codes();
+    let load_options = ContentLoadOptions {
+        default_language: &blog_config.site.language,
+        languages: if blog_config.is_multilingual() { &language_codes } else { &[] },
+        timezone: blog_config.timezone(),
+    };
+
+    let mut all_contents = load_all_contents(&content_dir, &load_options)
         .map_err(|e| BuildError::ContentLoaderError(e))?;
     output::info(&format!("{} content(s) loaded", all_contents.len()));
 
+    if blog_config.build.git_dates {
+        apply_git_history(&content_dir, &mut all_contents);
+    }
+
+    // Front matter `template:` / `layout:` must point to an existing theme template
+    for content in &all_contents {
+        if content.front_matter.template.is_none() && content.front_matter.layout.is_none() {
+            continue;
+        }
+        let template_name = content_template_name(&theme_package, content)
+            .map_err(|reason| BuildError::ContentTemplateError { path: content.meta.source_path.clone(), reason })?;
+        if theme_package.find_template(template_name).is_none() {
+            return Err(BuildError::ContentTemplateError {
+                path: content.meta.source_path.clone(),
+                reason: format!("template '{}' not found in {}", template_name, theme_package.templates_dirs[0].display()),
+            });
+        }
+    }
+
+    // Data files (site.data)
+    let site_data = load_data_dir(&content_dir.join("data"))?;
+    if !site_data.is_empty() {
+        let keys: Vec<&str> = site_data.keys().map(|k| k.as_str()).collect();
+        output::info(&format!("Data loaded: {}", keys.join(", ")));
+    }
+
+    //------------------------------------------------------------------------------
+    // Build contexts for each language (a single pass for single-language sites)
+    //------------------------------------------------------------------------------
+    let mut image_pipeline = ImagePipeline::new(project_path, &blog_config.images, &blog_config.site.path);
+
+    let mut language_sites = Vec::new();
+    for language in &language_codes {
+        language_sites.push(build_language_site(
+            project_path,
+            blog_config,
+            &theme_package,
+            language,
+            &all_contents,
+            &mut image_pipeline,
+        )?);
+    }
+    let image_pipeline = Arc::new(image_pipeline);
+
+    for language_site in &mut language_sites {
+        language_site.site_context.data = site_data.clone();
+    }
+
+    // Link translations across languages (language switchers)
+    let mut context_groups: Vec<&mut Vec<ContentContext>> = language_sites.iter_mut()
+        .flat_map(|site| [&mut site.post_contexts, &mut site.page_contexts])
+        .collect();
+    context_builder::link_translations(blog_config, &mut context_groups);
+
+    // Query functions (get_posts, get_page, get_taxonomy) over the final contexts
+    let mut queries = SiteQueries::new();
+    for language_site in &language_sites {
+        queries.add_language(
+            &language_site.language,
+            (&language_site.post_contexts, &language_site.render_posts),
+            (&language_site.page_contexts, &language_site.render_pages),
+            &language_site.site_context.taxonomies,
+        );
+    }
+    functions::register_all(&mut template_env, queries);
+    images::register_all(&mut template_env, Some(Arc::clone(&image_pipeline)));
+
+    //------------------------------------------------------------------------------
+    // Rendering each language (contents, taxonomies, archives, home, feed)
+    //------------------------------------------------------------------------------
+    for language_site in &language_sites {
+        if blog_config.is_multilingual() {
+            output::step(&format!("Rendering language '{}' ({})...", language_site.language, blog_config.language_name(&language_site.language)));
+        }
+        render_language_site(blog_config, &theme_package, &template_env, language_site, sink)?;
+    }
+
+    // Pages outside the per-language tree use the default language
+    let site_context = &language_sites[0].site_context;
+
+    //------------------------------------------------------------------------------
+    // Rendering extra templates
+    //------------------------------------------------------------------------------    
+    for extra_template in &theme_package.manifest.template_extra {
+        let template_name = extra_template.file.clone();
+        let template = template_env.get_template(template_name.as_str())
+            .map_err(|e| template_load_error(&template_name, e))?;
+
+        let extra_html = template.render(context! {
+            site => site_context,
+        }).map_err(|e| template_error(e, &extra_template.url))?;
+
+        write_file(&extra_template.output, sink, &extra_template.url, &extra_html)?;
+
+        output::success(&format!("Extra template rendered: {} -> {}", extra_template.url, extra_template.output));
+    }
+
+    //------------------------------------------------------------------------------
+    // Rendering 404 page
+    //------------------------------------------------------------------------------
+    if let Some(not_found_template_name) = &theme_package.manifest.template_default.not_found {
+        let not_found_template = template_env.get_template(not_found_template_name.as_str())
+            .map_err(|e| template_load_error(not_found_template_name, e))?;
+
+        let not_found_html = not_found_template.render(context! {
+            site => site_context,
+        }).map_err(|e| template_error(e, "404.html"))?;
+
+        write_file("404.html", sink, "/", &not_found_html)?;
+
+        output::success("404 page rendered: 404.html");
+    }
+
+    //------------------------------------------------------------------------------
+    // Copy static files
+    //------------------------------------------------------------------------------    
+    if theme_assets.is_empty() {
+        output::info("No Assets directory found, skipping copy");
+    } else {
+        write_theme_assets(&theme_assets, asset_manifest.as_ref(), sink)?;
+    }
+
+    let copy_tasks = [
+        (&content_dir.join("images"), "images", "Images", blog_config.images.strip_gps),
+        (&content_dir.join("data"), "data", "Data", false),
+    ];
+
+    for (src, dest, label, strip_gps) in copy_tasks {
+        if src.is_dir() {
+            copy_dir_recursive(src, sink, dest, strip_gps)?;
+            output::success(&format!("{label} copied successfully"));
+        } else {
+            output::info(&format!("No {label} directory found, skipping copy"));
+        }
+    }    
+
+    if blog_config.images.enabled {
+        let outputs = image_pipeline.outputs();
+        for (rel_path, cache_path) in &outputs.files {
+            sink.copy(cache_path, rel_path)
+                .map_err(|e| BuildError::Io { path: cache_path.clone(), source: e })?;
+        }
+        output::success(&format!("{} image variant(s) written ({} generated, {} from cache)",
+            outputs.files.len(), outputs.generated, outputs.cached));
+    }
+
+    //------------------------------------------------------------------------------
+    // Generate sitemap.xml
+    //------------------------------------------------------------------------------
+    if blog_config.build.sitemap {
+        output::step("Generating sitemap...");
+
+        let sitemap_xml = generate_sitemap(&language_sites);
+        write_file("sitemap.xml", sink, "/", &sitemap_xml)?;
+
+        output::success("Sitemap generated: sitemap.xml");
+    }
+
+    //------------------------------------------------------------------------------
+    // Generate robots.txt
+    //------------------------------------------------------------------------------
+    if blog_config.build.robots_txt {
+        output::step("Generating robots.txt...");
+
+        let robots_txt = format!("User-agent: *\nDisallow: /data\nAllow: /\nSitemap: {}/sitemap.xml\n",
+            site_context.base_url.trim_end_matches('/'));
+        write_file("robots.txt", sink, "/", &robots_txt)?;
+
+        output::success("robots.txt generated");
+    }
+
+    //------------------------------------------------------------------------------
+    // Generate _headers (Content-Security-Policy), once every page has been written
+    //------------------------------------------------------------------------------
+    if let Some(csp_sink) = &mut csp_sink {
+        output::step("Generating _headers...");
+
+        let pages = csp_sink.collector().inline_handler_pages();
+        if !pages.is_empty() {
+            let more = if pages.len() > 5 { format!(" and {} more", pages.len() - 5) } else { String::new() };
+            output::warning(&format!("Inline event handlers or javascript: URLs are blocked by the Content-Security-Policy: {}{}",
+                pages.iter().take(5).copied().collect::<Vec<_>>().join(", "), more));
+        }
+
+        let headers = csp_sink.headers_file();
+        write_file("_headers", csp_sink, "/", &headers)?;
+
+        output::success("_headers generated");
+    }
+
+    Ok(())
+}
+
+// Per-language build state
+struct LanguageSite<'a> {
+    language: String,
+    prefix: String,     // URL prefix (e.g., "/ko"), empty for the default language
+    render_posts: Vec<&'a ContentSource>,
+    render_pages: Vec<&'a ContentSource>,
+    post_contexts: Vec<ContentContext>,
+    page_contexts: Vec<ContentContext>,
+    site_context: SiteContext,
+    strings: UiStrings,
+}
+
+// Fills `updated` (when not set in front matter) and `git` from the last commit of each source file.
+// Not fatal: without git or a repository, the build continues with file dates.
+fn apply_git_history(content_dir: &Path, contents: &mut [ContentSource]) {
+    let commits = match load_last_commits(content_dir) {
+        Ok(commits) => commits,
+        Err(e) => {
+            output::warning(&format!("build.git_dates is enabled but git history is unavailable\n{}", e));
+            return;
+        }
+    };
+
+    let mut matched = 0;
+    for content in contents.iter_mut() {
+        let source_path = &content.meta.source_path;
+        let source_path = source_path.canonicalize().unwrap_or_else(|_| source_path.clone());
+
+        if let Some(info) = commits.get(&source_path) {
+            content.updated.get_or_insert(info.date);
+            content.git = Some(info.clone());
+            matched += 1;
+        }
+    }
+    output::info(&format!("git history found for {} content(s)", matched));
+}
+
+fn build_language_site<'a>(
+    project_path: &Path,
+    blog_config: &BlogConfig,
+    theme_package: &ThemePackage,
+    language: &str,
+    all_contents: &'a [ContentSource],
+    image_pipeline: &mut ImagePipeline,
+) -> Result<LanguageSite<'a>, BuildError> {
+    let prefix = blog_config.language_prefix(language);
+
+    // UI strings: theme i18n/<lang>.yaml, overridden by the project's i18n/<lang>.yaml
+    // Parent themes first (later wins), then the project's i18n/
+    let project_i18n_dir = project_path.join("i18n");
+    let i18n_dirs: Vec<&Path> = theme_package.i18n_dirs.iter().rev()
+        .map(|dir| dir.as_path())
+        .chain([project_i18n_dir.as_path()])
+        .collect();
+    let strings = UiStrings::load(language, &i18n_dirs)?;
+
     //------------------------------------------------------------------------------
     // Filter contents to render
     //------------------------------------------------------------------------------
     let (mut render_posts, mut render_pages): (Vec<&ContentSource>, Vec<&ContentSource>) = all_contents
         .iter()
         .filter(|content| blog_config.build.include_drafts || !content.front_matter.draft)
+        .filter(|content| !blog_config.is_multilingual() || content.language == language)
         .partition(|content| content.kind == ContentKind::Post);
 
     // Posts: sort by date
-    render_posts.sort_by(|a, b| b.front_matter.date.cmp(&a.front_matter.date));
-    output::info(&format!("{} post(s) to render", render_posts.len()));
+    render_posts.sort_by_key(|post| Reverse(post.date));
+    output::info(&format!("{} post(s) to render{}", render_posts.len(), language_label(blog_config, language)));
 
     // Pages: sort by title
     render_pages.sort_by(|a, b| a.front_matter.title.cmp(&b.front_matter.title));
-    output::info(&format!("{} page(s) to render", render_pages.len()));
+    output::info(&format!("{} page(s) to render{}", render_pages.len(), language_label(blog_config, language)));
 
     //------------------------------------------------------------------------------
     // Build taxonomies & archives index
     //------------------------------------------------------------------------------
-    let taxonomies_index = context_builder::build_taxonomies_index(&render_posts, &theme_package.manifest.taxonomies);
-    let archives_index = context_builder::build_archives_index(&render_posts, &theme_package.manifest.archives);
+    let taxonomies_index = context_builder::build_taxonomies_index(&render_posts, &theme_package.manifest.taxonomies, &prefix);
+    let archives_index = context_builder::build_archives_index(&render_posts, &theme_package.manifest.archives, &prefix);
 
     //------------------------------------------------------------------------------
     // Build posts & pages contexts
     //------------------------------------------------------------------------------
-    let mut post_contexts = context_builder::build_content_contexts(&blog_config, &render_posts, &taxonomies_index);
+    let mut post_contexts = context_builder::build_content_contexts(blog_config, &render_posts, &taxonomies_index);
     context_builder::link_prev_next_posts(&mut post_contexts);
 
-    let page_contexts = context_builder::build_content_contexts(&blog_config, &render_pages, &taxonomies_index);  
+    let mut page_contexts = context_builder::build_content_contexts(blog_config, &render_pages, &taxonomies_index);
+
+    // Markdown images: dimensions, lazy-loading, captions, and <picture> / srcset with resized variants
+    for (context, source) in post_contexts.iter().zip(&render_posts).chain(page_contexts.iter().zip(&render_pages)) {
+        image_pipeline.add_content_images(&context.url, &source.images);
+    }
+    for context in post_contexts.iter_mut().chain(page_contexts.iter_mut()) {
+        context.content_html = image_pipeline.rewrite_html(&context.content_html, &context.url)?;
+    }
 
     //------------------------------------------------------------------------------
     // Build site context
     //------------------------------------------------------------------------------
     let site_context = context_builder::build_site_context(
-        &blog_config,         
+        blog_config,
+        language,
         &taxonomies_index,
         &archives_index,
         &post_contexts.iter()
             .take(theme_package.manifest.recent_posts.count)
             .map(|post| context_builder::build_post_list_item(post)).collect(),
         &theme_package.manifest.others,
+        &strings,
     );
 
+    Ok(LanguageSite {
+        language: language.to_string(),
+        prefix,
+        render_posts,
+        render_pages,
+        post_contexts,
+        page_contexts,
+        site_context,
+        strings,
+    })
+}
+
+/// Template environment of a theme: loader (child theme first), filters and URL helpers.
+///
+/// Query functions (`get_posts`, ...) are registered separately, once the contents are built.
+pub fn create_template_env(blog_config: &BlogConfig, theme_package: &ThemePackage, theme_assets: &[ThemeAsset]) -> Environment<'static> {
+    let mut template_env = Environment::new();
+    // Child theme first, then the parent themes (`extends`)
+    let loaders: Vec<_> = theme_package.templates_dirs.iter().map(minijinja::path_loader).collect();
+    template_env.set_loader(move |name| {
+        for loader in &loaders {
+            if let Some(source) = loader(name)? {
+                return Ok(Some(source));
+            }
+        }
+        Ok(None)
+    });
+    template_env.set_debug(true);    // Keeps template sources for error snippets
+    if blog_config.build.strict_templates {
+        // Any use of an undefined value (e.g., `post.titel`, `{% if post.titel %}`) fails;
+        // optional values are checked with `is defined` or the `default` filter
+        template_env.set_undefined_behavior(UndefinedBehavior::Strict);
+    }
+    filters::register_all(&mut template_env, blog_config.timezone());
+    url_helpers::register_all(&mut template_env, blog_config, theme_assets);
+
+    template_env
+}
+
+// " (ko)" suffix for log messages on multilingual sites
+fn language_label(blog_config: &BlogConfig, language: &str) -> String {
+    if blog_config.is_multilingual() {
+        format!(" ({})", language)
+    } else {
+        String::new()
+    }
+}
+
+fn render_language_site(
+    blog_config: &BlogConfig,
+    theme_package: &ThemePackage,
+    template_env: &Environment,
+    language_site: &LanguageSite,
+    sink: &mut dyn OutputSink,
+) -> Result<(), BuildError> {
+    let site_context = &language_site.site_context;
+    let prefix = &language_site.prefix;
+    let strings = &language_site.strings;
+
     //------------------------------------------------------------------------------
     // Rendering posts and pages
     //------------------------------------------------------------------------------
     let render_content_tasks = [
-        ("post(s)", &post_contexts, &render_posts, theme_package.manifest.template_default.post),
-        ("page(s)", &page_contexts, &render_pages, theme_package.manifest.template_default.page),
+        ("post(s)", &language_site.post_contexts, &language_site.render_posts),
+        ("page(s)", &language_site.page_contexts, &language_site.render_pages),
     ];
 
-    for (label, contexts, sources, template_name) in &render_content_tasks {
-        let content_template = template_env.get_template(template_name.as_str())
-            .map_err(|_| BuildError::TemplateNotFound { template: template_name.clone() })?;
-
-        render_contents(&content_template, contexts, sources, &site_context, &output_dir)?;
+    for (label, contexts, sources) in &render_content_tasks {
+        render_contents(theme_package, template_env, contexts, sources, site_context, blog_config.images.strip_gps, sink)?;
 
         output::success(&format!("{} {} rendered", contexts.len(), label));
     }
@@ -169,12 +558,12 @@ pub fn run(root: &str) -> Result<(), BuildError> {
     //------------------------------------------------------------------------------
     let list_template_name = theme_package.manifest.template_default.list.clone();
     let list_template = template_env.get_template(list_template_name.as_str())
-        .map_err(|_| BuildError::TemplateNotFound { template: list_template_name.clone() })?;
+        .map_err(|e| template_load_error(&list_template_name, e))?;
 
     // Taxonomies
     for taxonomy_config in &theme_package.manifest.taxonomies {
         let post_contexts_by_term = context_builder::group_posts_by_taxonomy(
-            &post_contexts,
+            &language_site.post_contexts,
             &taxonomy_config.name,
         );
 
@@ -184,8 +573,11 @@ pub fn run(root: &str) -> Result<(), BuildError> {
             }
 
             let slug = filters::slugify(term);
-            let base_url = taxonomy_config.permalink.replace(":slug", &slug);
-            let title = format!("{}: {}", taxonomy_config.label, term);
+            let base_url = format!("{}{}", prefix, taxonomy_config.permalink.replace(":slug", &slug));
+            let title = strings.format("taxonomy_title", &[
+                ("label", &strings.taxonomy_label(&taxonomy_config.name, &taxonomy_config.label)),
+                ("term", term),
+            ]);
             let list_kind = ListKind::Taxonomy {
                 name: taxonomy_config.name.clone(),
                 slug: slug,
@@ -196,9 +588,10 @@ pub fn run(root: &str) -> Result<(), BuildError> {
                 &base_url,
                 taxonomy_config.per_page,
                 &title,
-                &site_context,
+                site_context,
                 &list_kind,
-                &output_dir,
+                strings,
+                sink,
             )?;
         }
         output::success(&format!("{} taxonomy term(s) rendered for '{}'", post_contexts_by_term.len(), taxonomy_config.name));
@@ -206,7 +599,7 @@ pub fn run(root: &str) -> Result<(), BuildError> {
 
     // Archives
     for archive_config in &theme_package.manifest.archives {
-        let post_contexts_by_archive = context_builder::group_posts_by_archive(&post_contexts, &archive_config.kind);
+        let post_contexts_by_archive = context_builder::group_posts_by_archive(&language_site.post_contexts, &archive_config.kind);
 
         for (archive, contexts) in &post_contexts_by_archive {
             if contexts.is_empty() {
@@ -220,7 +613,7 @@ pub fn run(root: &str) -> Result<(), BuildError> {
                     archive_config.permalink.replace(":year", &format!("{:04}", year))
                         .replace("/:month", "")
                         .replace("/:day", "")
-                    , format!("Archive: {:04}", year)
+                    , strings.format("archive_yearly", &[("year", &format!("{:04}", year))])
                     )
                 },
                 ArchiveKind::Monthly => {
@@ -230,7 +623,10 @@ pub fn run(root: &str) -> Result<(), BuildError> {
                     archive_config.permalink.replace(":year", &format!("{:04}", year))
                         .replace(":month", &format!("{:02}", month))
                         .replace("/:day", "")
-                    , format!("Archive: {:04}-{:02}", year, month)
+                    , strings.format("archive_monthly", &[
+                        ("year", &format!("{:04}", year)),
+                        ("month", &format!("{:02}", month)),
+                    ])
                     )   
                 },
                 ArchiveKind::Daily => {
@@ -241,10 +637,15 @@ pub fn run(root: &str) -> Result<(), BuildError> {
                     archive_config.permalink.replace(":year", &format!("{:04}", year))
                         .replace(":month", &format!("{:02}", month))
                         .replace(":day", &format!("{:02}", day))
-                    , format!("Archive: {:04}-{:02}-{:02}", year, month, day)
+                    , strings.format("archive_daily", &[
+                        ("year", &format!("{:04}", year)),
+                        ("month", &format!("{:02}", month)),
+                        ("day", &format!("{:02}", day)),
+                    ])
                     )                    
                 },
             };
+            let base_url = format!("{}{}", prefix, base_url);
 
             let list_kind = ListKind::Archive { year: archive.year, month: archive.month, day: archive.day };
 
@@ -253,9 +654,10 @@ pub fn run(root: &str) -> Result<(), BuildError> {
                 &base_url,
                 archive_config.per_page,
                 &title,
-                &site_context,
+                site_context,
                 &list_kind,
-                &output_dir,
+                strings,
+                sink,
             )?;
         }
         output::success(&format!("{} archive(s) rendered for '{:?}'", post_contexts_by_archive.len(), archive_config.kind));
@@ -266,111 +668,84 @@ pub fn run(root: &str) -> Result<(), BuildError> {
     //------------------------------------------------------------------------------
     let home_template_name = theme_package.manifest.template_default.home.clone();
     let home_template = template_env.get_template(home_template_name.as_str())
-        .map_err(|_| BuildError::TemplateNotFound { template: home_template_name.clone() })?;
+        .map_err(|e| template_load_error(&home_template_name, e))?;
 
-    let base_url = "/";
-    let title = "Home";
+    let base_url = &site_context.home_url;
+    let title = strings.get("home");
     let list_kind = ListKind::Home;
 
     render_list(
         &home_template,
-        &post_contexts,
+        &language_site.post_contexts,
         base_url,
         theme_package.manifest.pagination.default,
         title,
-        &site_context,
+        site_context,
         &list_kind,
-        &output_dir,
+        strings,
+        sink,
     )?;
     output::success("Home page rendered");
 
-    //------------------------------------------------------------------------------
-    // Rendering extra templates
-    //------------------------------------------------------------------------------    
-    for extra_template in &theme_package.manifest.template_extra {
-        let template_name = extra_template.file.clone();
-        let template = template_env.get_template(template_name.as_str())
-            .map_err(|_| BuildError::TemplateNotFound { template: template_name.clone() })?;
-
-        let extra_html = template.render(context! {
-            site => site_context,
-        }).map_err(|e| BuildError::ConvertError(format!("Template rendering error: {}", e)))?;
-
-        write_file(&extra_template.output, &output_dir, &extra_template.url, &extra_html)?;
-
-        output::success(&format!("Extra template rendered: {} -> {}", extra_template.url, extra_template.output));
-    }
-
-    //------------------------------------------------------------------------------
-    // Copy static files
-    //------------------------------------------------------------------------------    
-    let copy_tasks = [
-        (&theme_package.assets_dir, output_dir.join("assets"), "Assets"),
-        (&content_dir.join("images"), output_dir.join("images"), "Images"),
-        (&content_dir.join("data"), output_dir.join("data"), "Data"),
-    ];
-
-    for (src, dest, label) in copy_tasks {
-        if src.is_dir() {
-            copy_dir_recursive(src, &dest)?;
-            output::success(&format!("{label} copied successfully"));
-        } else {
-            output::info(&format!("No {label} directory found, skipping copy"));
-        }
-    }    
-
     //------------------------------------------------------------------------------
     // Generate RSS feed
     //------------------------------------------------------------------------------
     if blog_config.build.rss {
         output::step("Generating RSS feed...");
 
-        let rss_xml = generate_rss(&blog_config, &site_context, &post_contexts);
-        write_file("rss.xml", &output_dir, "/", &rss_xml)?;
-
-        output::success("RSS feed generated: rss.xml");
-    }
-    
-    //------------------------------------------------------------------------------
-    // Generate sitemap.xml
-    //------------------------------------------------------------------------------
-    if blog_config.build.sitemap {
-        output::step("Generating sitemap...");
-
-        let sitemap_xml = generate_sitemap(&site_context, &post_contexts, &page_contexts);
-        write_file("sitemap.xml", &output_dir, "/", &sitemap_xml)?;
+        let rss_xml = generate_rss(blog_config, site_context, &language_site.post_contexts);
+        write_file("rss.xml", sink, &site_context.home_url, &rss_xml)?;
 
-        output::success("Sitemap generated: sitemap.xml");
+        output::success(&format!("RSS feed generated: {}rss.xml", site_context.home_url.trim_start_matches('/')));
     }
 
-    //------------------------------------------------------------------------------
-    // Generate robots.txt
-    //------------------------------------------------------------------------------
-    if blog_config.build.robots_txt {
-        output::step("Generating robots.txt...");
+    Ok(())
+}
 
-        let robots_txt = format!("User-agent: *\nDisallow: /data\nAllow: /\nSitemap: {}/sitemap.xml\n",
-            site_context.base_url.trim_end_matches('/'));
-        write_file("robots.txt", &output_dir, "/", &robots_txt)?;
+// Writes the theme assets to assets/ (copied files, compiled Sass, minified CSS/JS)
+// and assets/manifest.json for fingerprinted builds
+fn write_theme_assets(
+    theme_assets: &[ThemeAsset],
+    manifest: Option<&BTreeMap<String, String>>,
+    sink: &mut dyn OutputSink,
+) -> Result<(), BuildError> {
+    let mut generated = 0;
+    for asset in theme_assets {
+        // Fingerprinted files also keep their original name: imports between assets
+        // (`import "./util.mjs"`, `@import "base.css"`) still point to it
+        let mut dst_paths = vec![format!("assets/{}", asset.output_path())];
+        if asset.fingerprint.is_some() {
+            dst_paths.push(format!("assets/{}", asset.path));
+        }
 
-        output::success("robots.txt generated");
+        for dst_path in &dst_paths {
+            match &asset.data {
+                AssetData::File(src_path) => sink.copy(src_path, dst_path)
+                    .map_err(|e| BuildError::Io { path: src_path.clone(), source: e })?,
+                AssetData::Generated(bytes) => sink.write(dst_path, bytes)
+                    .map_err(|e| BuildError::Io { path: sink.location(dst_path), source: e })?,
+            }
+        }
+        if matches!(asset.data, AssetData::Generated(_)) {
+            generated += 1;
+        }
     }
 
-    //------------------------------------------------------------------------------
-    eprintln!();
-    output::success("Build completed successfully");
+    if let Some(manifest) = manifest {
+        let manifest_json = serde_json::to_string_pretty(manifest).unwrap_or_default();
+        write_file("manifest.json", sink, "/assets/", &manifest_json)?;
+    }
 
+    if generated > 0 {
+        output::success(&format!("Assets copied successfully ({} file(s) compiled or minified)", generated));
+    } else {
+        output::success("Assets copied successfully");
+    }
     Ok(())
 }
 
 // Copy directory recursively (including subdirectories)
-fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), BuildError> {
-    // Create destination directory if it doesn't exist
-    if !dst.is_dir() {
-        fs::create_dir_all(dst)
-            .map_err(|e| BuildError::Io { path: dst.to_path_buf(), source: e })?;
-    }
-
+fn copy_dir_recursive(src: &Path, sink: &mut dyn OutputSink, dst: &str, strip_gps: bool) -> Result<(), BuildError> {
     // Read source directory
     let entries = fs::read_dir(src)
         .map_err(|e| BuildError::Io { path: src.to_path_buf(), source: e })?;
@@ -381,18 +756,17 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), BuildError> {
         
         let src_path = entry.path();
         let file_name = entry.file_name();
-        let dst_path = dst.join(&file_name);
+        let dst_path = format!("{}/{}", dst.trim_end_matches('/'), file_name.to_string_lossy());
 
         let file_type = entry.file_type()
             .map_err(|e| BuildError::Io { path: src_path.clone(), source: e })?;
 
         if file_type.is_dir() {
             // Recursively copy subdirectory
-            copy_dir_recursive(&src_path, &dst_path)?;
+            copy_dir_recursive(&src_path, sink, &dst_path, strip_gps)?;
         } else if file_type.is_file() {
             // Copy file
-            fs::copy(&src_path, &dst_path)
-                .map_err(|e| BuildError::Io { path: src_path.clone(), source: e })?;
+            copy_image(&src_path, sink, &dst_path, strip_gps)?;
         } else if file_type.is_symlink() {
             // Ignore symlinks
             output::warning(&format!("Skipping symlink at {:?}", src_path.display()));
@@ -402,15 +776,102 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), BuildError> {
     Ok(())
 }
 
+// Copies a file, removing the GPS metadata of JPEGs when `strip_gps` is set
+fn copy_image(src: &Path, sink: &mut dyn OutputSink, dst: &str, strip_gps: bool) -> Result<(), BuildError> {
+    let result = if strip_gps && images::is_jpeg(src) {
+        fs::read(src).and_then(|data| sink.write(dst, &images::strip_gps(&data)))
+    } else {
+        sink.copy(src, dst)
+    };
+    result.map_err(|e| BuildError::Io { path: src.to_path_buf(), source: e })
+}
+
+// Missing template, or a template that fails to compile (syntax errors)
+pub(crate) fn template_load_error(template: &str, error: minijinja::Error) -> BuildError {
+    if error.kind() == minijinja::ErrorKind::TemplateNotFound {
+        BuildError::TemplateNotFound { template: template.to_string(), reason: error_reason(&error) }
+    } else {
+        template_error(error, "(loading template)")
+    }
+}
+
+// Template error with its location and a source snippet
+//   - `rendering`: what was being rendered (content source path or output URL)
+fn template_error(error: minijinja::Error, rendering: &str) -> BuildError {
+    let template = match (error.name(), error.line()) {
+        (Some(name), Some(line)) => format!("{}:{}", name, line),
+        (Some(name), None) => name.to_string(),
+        _ => "(unknown)".to_string(),
+    };
+
+    let snippet = match (error.template_source(), error.line()) {
+        (Some(source), Some(line)) => format!("\n\n{}", template_snippet(source, line, error.range())),
+        _ => String::new(),
+    };
+
+    BuildError::TemplateError {
+        template,
+        rendering: rendering.to_string(),
+        reason: error_reason(&error),
+        snippet,
+    }
+}
+
+// "undefined value: ..." without the " (in post.html:12)" suffix of minijinja's Display
+fn error_reason(error: &minijinja::Error) -> String {
+    let mut reason = match error.detail() {
+        Some(detail) => format!("{}: {}", error.kind(), detail),
+        None => error.kind().to_string(),
+    };
+    if let Some(source) = std::error::Error::source(error) {
+        reason.push_str(&format!(" ({})", source));
+    }
+    reason
+}
+
+// Lines around `line` (1-based), with the failing expression underlined
+//       11 | <h1>{{ post.title }}</h1>
+//   >   12 | <p>{{ post.titel }}</p>
+//          |        ^^^^^^^^^^
+fn template_snippet(source: &str, line: usize, range: Option<std::ops::Range<usize>>) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    let first = line.saturating_sub(3);
+    let last = (line + 1).min(lines.len());
+
+    // Column of the failing expression, if it starts on the error line
+    let line_start: usize = lines.iter().take(line - 1).map(|l| l.len() + 1).sum();
+    let marker = range.and_then(|range| {
+        let line_text = lines.get(line - 1)?;
+        let start = range.start.checked_sub(line_start).filter(|&s| s <= line_text.len())?;
+        let end = (range.end - line_start).min(line_text.len());
+        let column = line_text.get(..start)?.chars().count();
+        let width = line_text.get(start..end).map(|s| s.chars().count()).unwrap_or(1).max(1);
+        Some(format!("{}| {}{}", " ".repeat(10), " ".repeat(column), "^".repeat(width)))
+    });
+
+    let mut out = Vec::new();
+    for (i, text) in lines.iter().enumerate().take(last).skip(first) {
+        let number = i + 1;
+        let prefix = if number == line { ">" } else { " " };
+        out.push(format!("  {} {:>5} | {}", prefix, number, text));
+        if number == line && let Some(marker) = &marker {
+            out.push(marker.clone());
+        }
+    }
+    out.join("\n")
+}
+
 // Function to write HTML content to index.html under given URL path
-fn write_file(filename: &str, output_dir: &Path, url_path: &str, data: &str) -> Result<(), BuildError> {
-    let dir = output_dir.join(url_path.trim_start_matches('/').trim_end_matches('/'));
-    fs::create_dir_all(&dir)
-        .map_err(|e| BuildError::Io { path: dir.clone(), source: e })?;
+fn write_file(filename: &str, sink: &mut dyn OutputSink, url_path: &str, data: &str) -> Result<(), BuildError> {
+    let dir = url_path.trim_start_matches('/').trim_end_matches('/');
+    let rel_path = if dir.is_empty() {
+        filename.to_string()
+    } else {
+        format!("{}/{}", dir, filename)
+    };
 
-    let file = dir.join(filename);
-    fs::write(&file, data)
-        .map_err(|e| BuildError::Io { path: file.clone(), source: e })?;
+    sink.write(&rel_path, data.as_bytes())
+        .map_err(|e| BuildError::Io { path: sink.location(&rel_path), source: e })?;
 
     Ok(())
 }
@@ -461,79 +922,93 @@ fn generate_rss(
 <rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom">
   <channel>
     <title>{}</title>
-    <link>{}</link>
+    <link>{}{}</link>
     <description>{}</description>
     <language>{}</language>
     <lastBuildDate>{}</lastBuildDate>
-    <atom:link href="{}/rss.xml" rel="self" type="application/rss+xml" />
+    <atom:link href="{}{}rss.xml" rel="self" type="application/rss+xml" />
 {}</channel>
 </rss>"#,
         escape_xml(&site_context.title),
         site_context.base_url.trim_end_matches('/'),
+        site_context.home_url.trim_end_matches('/'),
         escape_xml(&site_context.description),
         site_context.language.clone(),
         build_date,
         site_context.base_url.trim_end_matches('/'),
+        site_context.home_url,
         items
     )
 }
 
-// Escape XML special characters
-fn escape_xml(s: &str) -> String {
-    s.replace('&', "&amp;")
-     .replace('<', "&lt;")
-     .replace('>', "&gt;")
-     .replace('"', "&quot;")
-     .replace('\'', "&apos;")
-}
-
-// Generate sitemap.xml
-fn generate_sitemap(
-    site_context: &SiteContext,
-    post_contexts: &[ContentContext],
-    page_contexts: &[ContentContext],
-) -> String {
-    let base_url = site_context.base_url.trim_end_matches('/');
+// Generate sitemap.xml (all languages, with hreflang alternates for translations)
+fn generate_sitemap(language_sites: &[LanguageSite]) -> String {
+    let base_url = language_sites[0].site_context.base_url.trim_end_matches('/');
     let mut urls = Vec::new();
-    
-    // Home page
-    urls.push(format_sitemap_url(base_url, "/", None, "daily", "1.0"));
-    
-    // Posts
-    for post in post_contexts {
-        let lastmod = Some(post.updated.as_ref().unwrap_or(&post.date).to_rfc3339());
-        urls.push(format_sitemap_url(base_url, &post.url, lastmod, "weekly", "0.8"));
-    }
-    
-    // Pages
-    for page in page_contexts {
-        let lastmod = Some(page.updated.as_ref().unwrap_or(&page.date).to_rfc3339());
-        urls.push(format_sitemap_url(base_url, &page.url, lastmod, "monthly", "0.7"));
-    }
 
-    // taxonomy pages
-    for taxonomy_items in site_context.taxonomies.values() {
-        for item in taxonomy_items {
-            urls.push(format_sitemap_url(base_url, &item.url, None, "weekly", "0.6"));
+    // Home pages link to each other on multilingual sites
+    let home_alternates: Vec<(String, String)> = if language_sites.len() > 1 {
+        language_sites.iter()
+            .map(|site| (site.language.clone(), site.site_context.home_url.clone()))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    for language_site in language_sites {
+        let site_context = &language_site.site_context;
+
+        // Home page
+        urls.push(format_sitemap_url(base_url, &site_context.home_url, None, &home_alternates, "daily", "1.0"));
+        
+        // Posts
+        for post in &language_site.post_contexts {
+            let lastmod = Some(post.updated.as_ref().unwrap_or(&post.date).to_rfc3339());
+            urls.push(format_sitemap_url(base_url, &post.url, lastmod, &translation_alternates(post), "weekly", "0.8"));
+        }
+        
+        // Pages
+        for page in &language_site.page_contexts {
+            let lastmod = Some(page.updated.as_ref().unwrap_or(&page.date).to_rfc3339());
+            urls.push(format_sitemap_url(base_url, &page.url, lastmod, &translation_alternates(page), "monthly", "0.7"));
+        }
+
+        // taxonomy pages
+        for taxonomy_items in site_context.taxonomies.values() {
+            for item in taxonomy_items {
+                urls.push(format_sitemap_url(base_url, &item.url, None, &[], "weekly", "0.6"));
+            }
+        }
+        
+        // Archive pages
+        for archive in &site_context.archives {
+            urls.push(format_sitemap_url(base_url, &archive.url, None, &[], "monthly", "0.5"));
         }
-    }
-    
-    // Archive pages
-    for archive in &site_context.archives {
-        urls.push(format_sitemap_url(base_url, &archive.url, None, "monthly", "0.5"));
     }
     
     format!(r#"<?xml version="1.0" encoding="UTF-8"?>
-<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9" xmlns:xhtml="http://www.w3.org/1999/xhtml">
 {}
 </urlset>"#, urls.join("\n"))
 }
 
+// (hreflang, url) pairs for a translated content, including itself
+fn translation_alternates(context: &ContentContext) -> Vec<(String, String)> {
+    if context.translations.is_empty() {
+        return Vec::new();
+    }
+
+    std::iter::once((context.language.clone(), context.url.clone()))
+        .chain(context.translations.iter().map(|t| (t.language.clone(), t.url.clone())))
+        .collect()
+}
+
 // Format single sitemap URL entry
 fn format_sitemap_url(
     base_url: &str,
     path: &str,
     lastmod: Option<String>,
+    alternates: &[(String, String)],
     changefreq: &str,
     priority: &str,
 ) -> String {
@@ -541,20 +1016,27 @@ fn format_sitemap_url(
     let lastmod_tag = lastmod
         .map(|date| format!("\n    <lastmod>{}</lastmod>", date))
         .unwrap_or_default();
+    let alternate_tags: String = alternates.iter()
+        .map(|(hreflang, url)| format!("\n    <xhtml:link rel=\"alternate\" hreflang=\"{}\" href=\"{}\" />",
+            escape_xml(hreflang),
+            escape_xml(&format!("{}{}", base_url, url))))
+        .collect();
     
     format!(r#"  <url>
-    <loc>{}</loc>{}<changefreq>{}</changefreq>
+    <loc>{}</loc>{}{}
+    <changefreq>{}</changefreq>
     <priority>{}</priority>
   </url>"#,
         escape_xml(&full_url),
         lastmod_tag,
+        alternate_tags,
         changefreq,
         priority
     )
 }
 
 // Generate redirect page for /page/1/ to base URL
-fn create_page1_redirect(output_dir: &Path, base_url: &str) -> Result<(), BuildError> {
+fn create_page1_redirect(sink: &mut dyn OutputSink, base_url: &str) -> Result<(), BuildError> {
     let redirect_html = format!(r#"<!DOCTYPE html>
 <html>
 <head>
@@ -569,21 +1051,48 @@ fn create_page1_redirect(output_dir: &Path, base_url: &str) -> Result<(), BuildE
 </html>"#, base_url, base_url, base_url, base_url);
 
     let redirect_path = format!("{}/page/1/", base_url.trim_end_matches('/'));
-    write_file("index.html", output_dir, &redirect_path, &redirect_html)?;
+    write_file("index.html", sink, &redirect_path, &redirect_html)?;
     
     Ok(())
 }
 
 //------------------------------------------------------------------------------
+// Template of a content: front matter `template:` > `layout:` (theme.yaml `layouts`) > template_default
+fn content_template_name<'a>(theme_package: &'a ThemePackage, content: &'a ContentSource) -> Result<&'a str, String> {
+    let front_matter = &content.front_matter;
+    match (&front_matter.template, &front_matter.layout) {
+        (Some(_), Some(_)) => Err("both `template` and `layout` are set (use only one)".to_string()),
+        (Some(template), None) => Ok(template.as_str()),
+        (None, Some(layout)) => theme_package.manifest.layouts.get(layout)
+            .map(|file| file.as_str())
+            .ok_or_else(|| {
+                let layouts: Vec<&str> = theme_package.manifest.layouts.keys().map(|k| k.as_str()).collect();
+                format!("unknown layout '{}' (theme.yaml layouts: {})", layout,
+                    if layouts.is_empty() { "none".to_string() } else { layouts.join(", ") })
+            }),
+        (None, None) => Ok(match content.kind {
+            ContentKind::Post => theme_package.manifest.template_default.post.as_str(),
+            ContentKind::Page => theme_package.manifest.template_default.page.as_str(),
+        }),
+    }
+}
+
 fn render_contents(
-    content_template: &minijinja::Template,
+    theme_package: &ThemePackage,
+    template_env: &Environment,
     contexts: &[ContentContext],
     sources: &[&ContentSource],
     site_context: &SiteContext,
-    output_dir: &Path,
+    strip_gps: bool,
+    sink: &mut dyn OutputSink,
 ) -> Result<(), BuildError> {
     // contexts and sources are parallel arrays, so we can access source data (e.g., images) while rendering context
     for (context, source) in contexts.iter().zip(sources.iter()) {
+        let template_name = content_template_name(theme_package, source)
+            .map_err(|reason| BuildError::ContentTemplateError { path: source.meta.source_path.clone(), reason })?;
+        let content_template = template_env.get_template(template_name)
+            .map_err(|e| template_load_error(template_name, e))?;
+
         let rendered_html = match context.kind {
             ContentKind::Post => content_template.render(context! {
                 site => site_context,
@@ -593,18 +1102,17 @@ fn render_contents(
                 site => site_context,
                 page => context,
             }),
-        }.map_err(|e| BuildError::ConvertError(format!("Template rendering error: {}", e)))?;
+        }.map_err(|e| template_error(e, &source.meta.source_path.display().to_string()))?;
 
-        write_file("index.html", &output_dir, &context.url, &rendered_html)?;
+        write_file("index.html", sink, &context.url, &rendered_html)?;
 
         // Copy content images if any
         if !source.images.is_empty() {
-            let dest_dir = output_dir.join(context.url.trim_matches('/'));
+            let dest_dir = context.url.trim_matches('/');
             for img_path in &source.images {
                 let filename = img_path.file_name()
                     .ok_or_else(|| BuildError::ConvertError(format!("Invalid image path: {:?}", img_path)))?;
-                fs::copy(img_path, dest_dir.join(filename))
-                    .map_err(|e| BuildError::Io { path: img_path.clone(), source: e })?;
+                copy_image(img_path, sink, &format!("{}/{}", dest_dir, filename.to_string_lossy()), strip_gps)?;
             }
         }
     }
@@ -621,7 +1129,8 @@ fn render_list(
     title: &str,
     site_context: &SiteContext,
     list_kind: &ListKind,
-    output_dir: &Path,
+    strings: &UiStrings,
+    sink: &mut dyn OutputSink,
 ) -> Result<(), BuildError> {
     let list_items: Vec<PostListItem> = contexts.iter()
         .map(|post_ctx| context_builder::build_post_list_item(post_ctx))
@@ -636,6 +1145,7 @@ fn render_list(
             page_num,
             per_page,
             list_items.len(),
+            strings,
         );
 
         let list_context = context_builder::build_list_context(
@@ -659,7 +1169,7 @@ fn render_list(
                 site => site_context,
                 list => list_context,
             }),
-        }.map_err(|e| BuildError::ConvertError(format!("Template error: {}", e)))?;
+        }.map_err(|e| template_error(e, &list_context.url))?;
 
         let file_path = if page_num == 1 {
             base_url.to_string()
@@ -667,11 +1177,11 @@ fn render_list(
             format!("{}/page/{}/", base_url.trim_end_matches('/'), page_num)
         };
 
-        write_file("index.html", &output_dir, &file_path, &html)?;
+        write_file("index.html", sink, &file_path, &html)?;
 
     }
     if total_pages > 1 {
-        create_page1_redirect(&output_dir, &base_url)?;
+        create_page1_redirect(sink, base_url)?;
     }
 
     Ok(())
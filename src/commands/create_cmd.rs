@@ -59,6 +59,22 @@ build:
   rss_max_items: 20
   sitemap: true
   robots_txt: true
+
+server:
+  host: "127.0.0.1"
+  port: 8000
+  workers: 4
+  # access_log: "access.log"
+
+# Multilingual site (optional)
+#   Translations: post.md + post.ko.md, or a shared `translation_key` in front matter
+# languages:
+#   en:
+#     name: "English"
+#   ko:
+#     name: "한국어"
+#     prefix: "/ko"
+#     title: "내 블로그"
 "#;
         create_file_logged(&config_path.as_path(), default_config)?;
 
@@ -83,16 +99,88 @@ build:
         output::step("Creating default theme...");
 
         // Create themes root directory
-        let theme_rootdirs = [
-            "themes",
-            "themes/default",
-        ];
-        for dir in &theme_rootdirs {
-            create_dir_logged(&project_path.join(dir).as_path())?;
-        }
+        create_dir_logged(&project_path.join("themes"))?;
+        create_theme(&project_path.join("themes/default"), "default")?;
+
+        eprintln!();
+        output::success(&format!("Project '{}' created successfully", project_path.display()));
+    }
+
+    Ok(())
+}
+
+/// Creates a theme from the embedded default theme (theme.yaml, templates, i18n)
+///
+/// Used by `create` (themes/default) and `theme new`.
+pub fn create_theme(theme_dir: &Path, name: &str) -> Result<(), CreateError> {
+    create_dir_logged(theme_dir)?;
+
+    let theme_yaml = DEFAULT_THEME_YAML.replacen("name: \"default\"", &format!("name: \"{}\"", name), 1);
+    create_file_logged(&theme_dir.join("theme.yaml"), &theme_yaml)?;
+
+    // Create themes subdirectories
+    let theme_subdirs = [
+        "assets",
+        "assets/css",
+        "assets/js",
+        "assets/images",
+        "templates",
+        "i18n",
+    ];
+
+    for dir in &theme_subdirs {
+        create_dir_logged(&theme_dir.join(dir))?;
+    }
+
+    // Create template and i18n files
+    let theme_files = [
+        ("templates/base.html", BASE_HTML_TEMPLATE),
+        ("templates/home.html", HOME_HTML_TEMPLATE),
+        ("templates/list.html", LIST_HTML_TEMPLATE),
+        ("templates/post.html", POST_HTML_TEMPLATE),
+        ("templates/page.html", PAGE_HTML_TEMPLATE),
+        ("templates/404.html", NOT_FOUND_HTML_TEMPLATE),
+        ("i18n/ko.yaml", KO_STRINGS),
+    ];
+
+    for (file_path, content) in &theme_files {
+        create_file_logged(&theme_dir.join(file_path), content)?;
+    }
+
+    Ok(())
+}
+
+fn create_dir_logged(path: &Path) -> Result<(), CreateError> {
+    fs::create_dir_all(path).map_err(|e| CreateError::Io { path: path.to_path_buf(), source: e })?;
+
+    let mut dir_path_display = path_to_log_slash(path);
+    if !dir_path_display.ends_with('/') {
+        dir_path_display.push('/');
+    }
+
+    output::print_check(&dir_path_display);
+    Ok(())
+}
+
+fn create_file_logged(path: &Path, content: &str) -> Result<(), CreateError> {
+    fs::write(path, content).map_err(|e| CreateError::Io { path: path.to_path_buf(), source: e })?;
+
+    let file_path_display = path_to_log_slash(path);
+
+    output::print_file(&file_path_display);
+    Ok(())
+}
+
+fn path_to_log_slash(path: &Path) -> String {
+    let path_str = path.to_string_lossy().to_string();
+    path_str.replace(std::path::MAIN_SEPARATOR, "/")
+}
+
+//------------------------------------------------------------------------------
+// Default theme files
+//------------------------------------------------------------------------------
 
-        // Create default theme.yml
-        let default_theme_config = r#"meta:
+const DEFAULT_THEME_YAML: &str = r#"meta:
   name: "default"
   version: "1.0.0"
   author: "xnBlogGen"
@@ -103,6 +191,7 @@ template_default:
   list: "list.html"
   post: "post.html"
   page: "page.html"
+  not_found: "404.html"
 
 template_extra: []
 
@@ -132,23 +221,8 @@ recent_posts:
 # Any fields added here are accessible in templates via {{ site.theme.field_name }}
 # Examples: colors, fonts, social_links, features, etc.
 "#;
-        create_file_logged(&project_path.join("themes/default/theme.yaml").as_path(), default_theme_config)?;
-
-        // Create themes subdirectories
-        let theme_subdirs = [
-            ("themes/default/assets"),
-            ("themes/default/assets/css"),
-            ("themes/default/assets/js"),
-            ("themes/default/assets/images"),
-            ("themes/default/templates"),
-        ];
 
-        for dir in &theme_subdirs {
-            create_dir_logged(&project_path.join(dir).as_path())?;
-        }
-
-        // Create template files       
-        let base_html_template = r#"<!DOCTYPE html>
+const BASE_HTML_TEMPLATE: &str = r#"<!DOCTYPE html>
 <html lang="{{ site.language }}">
 <head>
   <title>{% block title %}{{ site.title }}{% endblock %}</title>
@@ -159,14 +233,21 @@ recent_posts:
   <meta name="description" content="{{ site.description }}">
   {% endif %}
 
-  <link rel="stylesheet" href="/assets/css/style.css">
+  <link rel="stylesheet" href="{{ asset_url('css/style.css') }}">
 </head>
 <body>
   <header>
-    <h1><a href="/">{{ site.title }}</a></h1>
+    <h1><a href="{{ site.home_url | safe }}">{{ site.title }}</a></h1>
     {% if site.description %}
       <p>{{ site.description }}</p>
     {% endif %}
+    {% if site.languages %}
+      <nav class="languages">
+        {% for lang in site.languages %}
+          {% if lang.is_current %}<strong>{{ lang.name }}</strong>{% else %}<a href="{{ lang.url | safe }}" hreflang="{{ lang.code }}">{{ lang.name }}</a>{% endif %}
+        {% endfor %}
+      </nav>
+    {% endif %}
   </header>
 
   <div id="layout">
@@ -185,8 +266,8 @@ recent_posts:
 </body>
 </html>
 "#;
-        
-        let home_html_template = r#"{% extends "base.html" %}
+
+const HOME_HTML_TEMPLATE: &str = r#"{% extends "base.html" %}
 
 {% block content %}
   <h2>Latest posts</h2>
@@ -214,7 +295,7 @@ recent_posts:
 {% endblock %}
 "#;
 
-        let list_html_template = r#"{% extends "base.html" %}
+const LIST_HTML_TEMPLATE: &str = r#"{% extends "base.html" %}
 
 {% block content %}
   <h2>{{ list.title }}</h2>
@@ -241,8 +322,8 @@ recent_posts:
   {% endif %}
 {% endblock %}
 "#;
-        
-        let post_html_template = r#"{% extends "base.html" %}
+
+const POST_HTML_TEMPLATE: &str = r#"{% extends "base.html" %}
 
 {% block content %}
   <article class="post">
@@ -258,6 +339,14 @@ recent_posts:
       {% endif %}
     </p>
 
+    {% if post.translations %}
+      <p class="post-translations">
+        {% for t in post.translations %}
+          <a href="{{ t.url | safe }}" hreflang="{{ t.language }}" lang="{{ t.language }}">{{ t.name }}</a>
+        {% endfor %}
+      </p>
+    {% endif %}
+
     <div class="post-body">
       {{ post.content_html | safe }}
     </div>
@@ -284,8 +373,8 @@ recent_posts:
   </article>
 {% endblock %}
 "#;
-        
-        let page_html_template = r#"{% extends "base.html" %}
+
+const PAGE_HTML_TEMPLATE: &str = r#"{% extends "base.html" %}
 
 {% block content %}
   <article class="page">
@@ -299,48 +388,35 @@ recent_posts:
 
 "#;
 
-        // Create theme files (theme.yml and template files)
-        let theme_files = [
-            ("themes/default/templates/base.html", base_html_template),
-            ("themes/default/templates/home.html", home_html_template),
-            ("themes/default/templates/list.html", list_html_template),
-            ("themes/default/templates/post.html", post_html_template),
-            ("themes/default/templates/page.html", page_html_template),
-        ];
-
-        for (file_path, content) in &theme_files {
-            create_file_logged(&project_path.join(file_path).as_path(), content)?;
-        }
-
-        eprintln!();
-        output::success(&format!("Project '{}' created successfully", project_path.display()));
-    }
-
-    Ok(())
-}
+const NOT_FOUND_HTML_TEMPLATE: &str = r#"{% extends "base.html" %}
 
-fn create_dir_logged(path: &Path) -> Result<(), CreateError> {
-    fs::create_dir_all(path).map_err(|e| CreateError::Io { path: path.to_path_buf(), source: e })?;
+{% block title %}Page not found - {{ site.title }}{% endblock %}
 
-    let mut dir_path_display = path_to_log_slash(path);
-    if !dir_path_display.ends_with('/') {
-        dir_path_display.push('/');
-    }
-
-    output::print_check(&dir_path_display);
-    Ok(())
-}
-
-fn create_file_logged(path: &Path, content: &str) -> Result<(), CreateError> {
-    fs::write(path, content).map_err(|e| CreateError::Io { path: path.to_path_buf(), source: e })?;
-
-    let file_path_display = path_to_log_slash(path);
+{% block content %}
+  <article class="page">
+    <h1>Page not found</h1>
 
-    output::print_file(&file_path_display);
-    Ok(())
-}
+    <p>The page you are looking for does not exist.</p>
+    <p><a href="{{ site.home_url | safe }}">← Back to home</a></p>
+  </article>
+{% endblock %}
+"#;
 
-fn path_to_log_slash(path: &Path) -> String {
-    let path_str = path.to_string_lossy().to_string();
-    path_str.replace(std::path::MAIN_SEPARATOR, "/")
-}
\ No newline at end of file
+// UI strings used by the generator (selected by site.language, e.g., i18n/ko.yaml)
+const KO_STRINGS: &str = r#"# UI strings for Korean sites (site.language: "ko")
+# Placeholders: {page}, {label}, {term}, {year}, {month}, {day}
+# Any key added here is available in templates via {{ site.i18n.key }}
+
+home: "홈"
+page: "{page} 페이지"
+first: "처음"
+last: "마지막"
+taxonomy_title: "{label}: {term}"
+archive_yearly: "{year}년 아카이브"
+archive_monthly: "{year}년 {month}월 아카이브"
+archive_daily: "{year}년 {month}월 {day}일 아카이브"
+
+# Taxonomy labels (overrides theme.yaml's label)
+taxonomy.tags: "태그"
+taxonomy.categories: "카테고리"
+"#;
@@ -3,4 +3,5 @@
 pub mod create_cmd;
 pub mod new_cmd;
 pub mod build_cmd;
-pub mod server_cmd;
\ No newline at end of file
+pub mod server_cmd;
+pub mod theme_cmd;
\ No newline at end of file
@@ -1,14 +1,25 @@
 // src/commands/server.rs
 
+use std::collections::BTreeMap;
 use std::fs;
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+use std::net::Ipv6Addr;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
-use std::time::Instant;
+use std::time::{Instant, UNIX_EPOCH};
 
-use tiny_http::{Server, Response, StatusCode};
+use chrono::{DateTime, Utc};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use tiny_http::{Server, Response, StatusCode, Method};
 use thiserror::Error;
 
+use crate::commands::build_cmd::{self, BuildError};
+use crate::config::blogconfig::{BlogConfig, BlogConfigError};
 use crate::utils::output;
+use crate::utils::output_sink::MemorySink;
 
 #[derive(Error, Debug)]
 pub enum ServerError {
@@ -18,7 +29,7 @@ pub enum ServerError {
     #[error("Blog project not found\n  Path: {path}\n  Expected: blogconfig.yaml")]
     ProjectNotFound{ path: PathBuf},
 
-    #[error("Publishing directory not found\n  Path: {path}\n  Expected: public/")]
+    #[error("Publishing directory not found\n  Path: {path}\n  Expected: build.output_dir (default: public/)")]
     PublishingDirNotFound{ path: PathBuf },
 
     #[error("Failed to start server\n  Reason: {0}")]
@@ -32,9 +43,77 @@ pub enum ServerError {
 
     #[error("Security violation: path traversal attempt detected")]
     PathTraversal,
+
+    #[error(transparent)]
+    BlogConfigError(#[from] BlogConfigError),
+
+    #[error(transparent)]
+    BuildError(#[from] BuildError),
+}
+
+// Result of mapping a request path onto the publishing directory
+enum ResolvedPath {
+    File(ServedFile),
+    Redirect(String),   // Directory requested without trailing slash
+}
+
+// What `handle_request` sends back for a successfully resolved request
+enum ResolvedResponse {
+    File(FileResponse, String),     // Response + served path (for logging)
+    Redirect(String),
 }
 
-pub fn run(port: u16, root: &str) -> Result<(), ServerError> {
+// File response prepared by `build_file_response`
+struct FileResponse {
+    status: u16,
+    headers: Vec<(&'static str, String)>,
+    body: Box<dyn Read + Send>,
+    content_length: usize,
+}
+
+// Parsed `Range` request header
+#[derive(Debug, PartialEq)]
+enum ByteRange {
+    Satisfiable { start: u64, end: u64 },   // inclusive
+    Unsatisfiable,
+}
+
+// Command line overrides for the `server:` section of blogconfig.yaml
+#[derive(Debug, Default)]
+pub struct ServerOptions {
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub workers: Option<usize>,
+    pub access_log: Option<String>,
+    pub memory: bool,   // Render into memory instead of serving the output directory
+    pub drafts: bool,   // Include drafts (memory mode only)
+}
+
+// Where the served files come from
+enum SiteSource {
+    Disk(PathBuf),          // Publishing directory (build output)
+    Memory(MemorySite),     // Site rendered by `server --memory`
+}
+
+// In-memory build output: site-relative path -> bytes
+struct MemorySite {
+    files: BTreeMap<String, Arc<Vec<u8>>>,
+    built_at: i64,
+}
+
+// A file resolved from a `SiteSource`
+enum ServedFile {
+    Disk(PathBuf),
+    Memory { path: String, data: Arc<Vec<u8>>, modified: i64 },
+}
+
+// Access log file shared by the worker threads (Common Log Format)
+struct AccessLog {
+    path: PathBuf,
+    file: Mutex<fs::File>,
+}
+
+pub fn run(root: &str, options: ServerOptions) -> Result<(), ServerError> {
     let project_path = PathBuf::from(root);
     let config_path = project_path.join("blogconfig.yaml");
 
@@ -42,103 +121,277 @@ pub fn run(port: u16, root: &str) -> Result<(), ServerError> {
         return Err(ServerError::ProjectNotFound{ path: project_path.clone() });
     }
 
-    let publishing_dir = project_path.join("public");
-    if !publishing_dir.is_dir() {
-        return Err(ServerError::PublishingDirNotFound{ path: publishing_dir.clone() });
-    }
+    // Load blog configuration (command line options take precedence over the server section)
+    let blog_config = BlogConfig::load_from_file(&config_path)
+        .map_err(ServerError::BlogConfigError)?;
 
-    if port == 0 || port > u16::MAX {
+    let host = options.host.unwrap_or(blog_config.server.host.clone());
+    let port = options.port.unwrap_or(blog_config.server.port);
+    let workers = options.workers.unwrap_or(blog_config.server.workers);
+    let access_log_path = options.access_log.or(blog_config.server.access_log.clone());
+
+    if port == 0 {
         return Err(ServerError::ServerStartFailed("Invalid port number".to_string()));
     }
 
-    let addr = format!("127.0.0.1:{}", port);
+    if workers == 0 {
+        return Err(ServerError::ServerStartFailed("Invalid worker count (must be at least 1)".to_string()));
+    }
+
+    // Memory mode renders the whole site without touching the output directory,
+    // so drafts can be previewed without ending up in the deployed files
+    let site = if options.memory {
+        let mut blog_config = blog_config;
+        if options.drafts {
+            blog_config.build.include_drafts = true;
+        }
+
+        output::step("Rendering site into memory...");
+        let mut sink = MemorySink::new();
+        build_cmd::build_site(&project_path, &blog_config, &mut sink)?;
+
+        let files = sink.into_files().into_iter()
+            .map(|(path, data)| (path, Arc::new(data)))
+            .collect::<BTreeMap<_, _>>();
+        output::success(&format!("{} file(s) rendered into memory", files.len()));
+        eprintln!();
+
+        SiteSource::Memory(MemorySite { files, built_at: Utc::now().timestamp() })
+    } else {
+        let publishing_dir = project_path.join(&blog_config.build.output_dir);
+        if !publishing_dir.is_dir() {
+            return Err(ServerError::PublishingDirNotFound{ path: publishing_dir.clone() });
+        }
+        SiteSource::Disk(publishing_dir)
+    };
+    let site = Arc::new(site);
+
+    // Relative access log paths are resolved against the project root
+    let access_log: Option<Arc<AccessLog>> = match &access_log_path {
+        Some(path) => {
+            let log_path = project_path.join(path);
+            let file = fs::OpenOptions::new().create(true).append(true).open(&log_path)
+                .map_err(|e| ServerError::Io { path: log_path.clone(), source: e })?;
+            Some(Arc::new(AccessLog { path: log_path, file: Mutex::new(file) }))
+        }
+        None => None,
+    };
+
+    // IPv6 literals need brackets ("[::1]:8000")
+    let addr = match host.parse::<Ipv6Addr>() {
+        Ok(_) => format!("[{}]:{}", host, port),
+        Err(_) => format!("{}:{}", host, port),
+    };
     let server = Server::http(&addr).map_err(|e| {
         ServerError::ServerStartFailed(format!("Could not bind to {}: {}", addr, e))
     })?;
+    let server = Arc::new(server);
+
+    // Ctrl+C unblocks the accept loop below; workers finish their current response, answer
+    // the still-queued requests with 503 and exit. A second Ctrl+C exits immediately.
+    let shutting_down = Arc::new(AtomicBool::new(false));
+    let shutdown_server = Arc::clone(&server);
+    let shutdown_flag = Arc::clone(&shutting_down);
+    ctrlc::set_handler(move || {
+        if shutdown_flag.swap(true, Ordering::SeqCst) {
+            std::process::exit(130);
+        }
+        shutdown_server.unblock();
+    }).map_err(|e| ServerError::ServerStartFailed(format!("Could not install Ctrl+C handler: {}", e)))?;
 
-    output::info(&format!("Starting server at http://{}/", addr));
-    output::print_path(&publishing_dir.display().to_string());
+    output::info(&format!("Starting server at http://{}/ ({} worker(s))", addr, workers));
+    match site.as_ref() {
+        SiteSource::Disk(publishing_dir) => output::print_path(&publishing_dir.display().to_string()),
+        SiteSource::Memory(_) => output::info("Serving the in-memory build (nothing is written to disk)"),
+    }
+    if let Some(path) = &access_log_path {
+        output::info(&format!("Writing access log to {}", path));
+    }
     output::info("Press Ctrl+C to stop the server");
     eprintln!();
 
+    // Bounded worker pool: requests queue up (up to a limit) instead of spawning a thread each
+    let (sender, receiver) = mpsc::sync_channel::<tiny_http::Request>(workers * 16);
+    let receiver = Arc::new(Mutex::new(receiver));
+
+    let worker_handles: Vec<thread::JoinHandle<()>> = (0..workers)
+        .map(|_| {
+            let receiver = Arc::clone(&receiver);
+            let site = Arc::clone(&site);
+            let access_log = access_log.clone();
+            let shutting_down = Arc::clone(&shutting_down);
+
+            thread::spawn(move || loop {
+                // The lock is released as soon as a request has been taken from the queue
+                let request = match receiver.lock() {
+                    Ok(receiver) => receiver.recv(),
+                    Err(_) => break,
+                };
+
+                match request {
+                    Ok(request) if shutting_down.load(Ordering::SeqCst) => respond_unavailable(request),
+                    Ok(request) => {
+                        if let Err(e) = handle_request(request, &site, access_log.as_deref()) {
+                            output::error(&format!("Request error: {}", e));
+                        }
+                    }
+                    Err(_) => break,   // Sender dropped: shutting down
+                }
+            })
+        })
+        .collect();
+
+    // Never blocks on a full queue, so Ctrl+C always ends the loop
     for request in server.incoming_requests() {
-        let publishing_dir = publishing_dir.clone();
+        match sender.try_send(request) {
+            Ok(()) => {}
+            Err(mpsc::TrySendError::Full(request)) => respond_unavailable(request),
+            Err(mpsc::TrySendError::Disconnected(_)) => break,
+        }
+    }
 
-        thread::spawn(move || {
-            if let Err(e) = handle_request(request, &publishing_dir) {
-                output::error(&format!("Request error: {}", e));
-            }
-        });
+    eprintln!();
+    output::step("Shutting down server...");
+
+    drop(sender);
+    for handle in worker_handles {
+        handle.join().ok();
     }
 
+    output::success("Server stopped");
+
     Ok(())
 }
 
+// 503 for requests that can't be served now (full queue, shutting down)
+fn respond_unavailable(request: tiny_http::Request) {
+    output::warning(&format!("{} {} - 503 Service Unavailable", request.method(), request.url()));
+    let response = Response::from_string("503 Service Unavailable")
+        .with_status_code(503)
+        .with_header(tiny_http::Header::from_bytes(&b"Retry-After"[..], &b"1"[..]).unwrap());
+    request.respond(response).ok();
+}
+
 fn handle_request(
     request: tiny_http::Request,
-    publishing_dir: &std::path::Path,
+    site: &SiteSource,
+    access_log: Option<&AccessLog>,
 ) -> Result<(), ServerError> {
-    let url_path = request.url();
-    let method = request.method();
+    let url_path = request.url().to_string();
+    let method = request.method().clone();
     let start_time = Instant::now();
 
+    // Captured up front: `respond` consumes the request
+    let remote_addr = request.remote_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_else(|| "-".to_string());
+    let http_version = request.http_version().clone();
+
+    // Query strings are not part of the file path, but must survive redirects
+    let (request_path, query) = match url_path.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (url_path.as_str(), None),
+    };
+
+    // HEAD requests get the same headers as GET, without reading the body
+    let head_only = method == Method::Head;
+
     // Process the request
     let result = (|| {
-        // 1. Validate and resolve path
-        let safe_path = validate_and_resolve_path(url_path, publishing_dir)?;
-
-        // 2. Resolve to actual file
-        let file_path = resolve_file_path(&safe_path)?;
+        // 1. Validate and resolve path to actual file
+        let resolved = match site {
+            SiteSource::Disk(publishing_dir) => {
+                let safe_path = validate_and_resolve_path(request_path, publishing_dir)?;
+                resolve_file_path(&safe_path, request_path, query)?
+            }
+            SiteSource::Memory(memory_site) => resolve_memory_path(memory_site, request_path, query)?,
+        };
 
-        // 3. Read file contents
-        let contents = fs::read(&file_path).map_err(|e| ServerError::Io {
-            path: file_path.clone(),
-            source: e,
-        })?;
+        let served_file = match resolved {
+            ResolvedPath::File(served_file) => served_file,
+            ResolvedPath::Redirect(location) => return Ok(ResolvedResponse::Redirect(location)),
+        };
 
-        // 4. Determine Content-Type
-        let content_type = get_content_type(&file_path);
+        // 2. Build the response (conditional requests, ranges, compression)
+        let file_response = build_file_response(&request, &served_file, head_only)?;
 
-        Ok((contents, content_type, file_path))
+        Ok(ResolvedResponse::File(file_response, served_file.display_path()))
     })();
 
     // Handle result and send appropriate response
-    match result {
-        Ok((contents, content_type, file_path)) => {
+    let (status_code, sent_bytes) = match result {
+        Ok(ResolvedResponse::File(file_response, file_path)) => {
             let elapsed = start_time.elapsed();
+            let status = StatusCode(file_response.status);
             output::info(&format!(
-                "{} {} - 200 OK ({} bytes, {:.2}ms)",
+                "{} {} - {} {} ({} bytes, {:.2}ms)",
                 method,
                 url_path,
-                contents.len(),
+                status.0,
+                status.default_reason_phrase(),
+                file_response.content_length,
                 elapsed.as_secs_f64() * 1000.0
             ));
-            output::print_path(&file_path.display().to_string());
+            output::print_path(&file_path);
 
-            let response = Response::from_data(contents).with_header(
-                tiny_http::Header::from_bytes(&b"Content-Type"[..], content_type.as_bytes())
-                    .unwrap(),
-            );
+            let headers = file_response.headers.iter()
+                .map(|(name, value)| tiny_http::Header::from_bytes(name.as_bytes(), value.as_bytes()).unwrap())
+                .collect();
+
+            // Explicit length + no chunking, so Content-Length is always sent (also for HEAD)
+            let response = Response::new(
+                status,
+                headers,
+                file_response.body,
+                Some(file_response.content_length),
+                None,
+            ).with_chunked_threshold(usize::MAX);
+
+            let sent_bytes = if head_only { 0 } else { file_response.content_length };
 
             request.respond(response).map_err(|e| {
                 ServerError::ServerStartFailed(format!("Failed to send response: {}", e))
             })?;
+
+            (status.0, sent_bytes)
+        }
+        Ok(ResolvedResponse::Redirect(location)) => {
+            output::info(&format!("{} {} - 301 Moved Permanently -> {}", method, url_path, location));
+
+            let response = Response::empty(StatusCode(301))
+                .with_header(
+                    tiny_http::Header::from_bytes(&b"Location"[..], location.as_bytes())
+                        .unwrap(),
+                );
+
+            request.respond(response).ok();
+
+            (301, 0)
         }
         Err(ServerError::FileNotFound { .. }) | Err(ServerError::InvalidPath(_)) => {
             output::warning(&format!("{} {} - 404 Not Found", method, url_path));
 
-            let body = b"404 Not Found";
-            let response = Response::from_data(&body[..])
+            // Serve the theme's 404.html if the build produced one (GitHub Pages / Netlify behaviour)
+            let (body, content_type) = match site.not_found_page() {
+                Some(html) => (html, "text/html; charset=utf-8"),
+                None => (b"404 Not Found".to_vec(), "text/plain; charset=utf-8"),
+            };
+            let sent_bytes = if head_only { 0 } else { body.len() };
+
+            let response = Response::from_data(body)
                 .with_status_code(StatusCode(404))
                 .with_header(
                     tiny_http::Header::from_bytes(
                         &b"Content-Type"[..],
-                        b"text/plain; charset=utf-8",
+                        content_type.as_bytes(),
                     )
                     .unwrap(),
-                );
+                )
+                .with_chunked_threshold(usize::MAX);
 
             request.respond(response).ok();
+
+            (404, sent_bytes)
         }
         Err(ServerError::PathTraversal) => {
             output::error(&format!("{} {} - 403 Forbidden (path traversal)", method, url_path));
@@ -155,6 +408,8 @@ fn handle_request(
                 );
 
             request.respond(response).ok();
+
+            (403, body.len())
         }
         Err(e) => {
             output::error(&format!("Internal server error: {}", e));
@@ -172,12 +427,289 @@ fn handle_request(
                 );
 
             request.respond(response).ok();
+
+            (500, body.len())
+        }
+    };
+
+    if let Some(access_log) = access_log {
+        // Common Log Format: host ident authuser [date] "request" status bytes
+        let line = format!(
+            "{} - - [{}] \"{} {} HTTP/{}.{}\" {} {}\n",
+            remote_addr,
+            chrono::Local::now().format("%d/%b/%Y:%H:%M:%S %z"),
+            method,
+            url_path,
+            http_version.0,
+            http_version.1,
+            status_code,
+            sent_bytes,
+        );
+        if let Ok(mut file) = access_log.file.lock() {
+            file.write_all(line.as_bytes())
+                .map_err(|e| ServerError::Io { path: access_log.path.clone(), source: e })?;
         }
     }
 
     Ok(())
 }
 
+/// Builds the response for a resolved file
+///
+/// - `ETag` / `Last-Modified` validators, answered with 304 on `If-None-Match` / `If-Modified-Since`
+/// - Single `Range` requests answered with 206 (416 if unsatisfiable), so media files can seek
+/// - gzip for text types when the client accepts it (full responses only)
+fn build_file_response(
+    request: &tiny_http::Request,
+    served_file: &ServedFile,
+    head_only: bool,
+) -> Result<FileResponse, ServerError> {
+    let content_type = served_file.content_type();
+    let (file_len, mtime) = served_file.metadata()?;
+
+    let etag = format!("\"{:x}-{:x}\"", mtime, file_len);
+    let gzip_etag = format!("\"{:x}-{:x}-gzip\"", mtime, file_len);
+    let last_modified = http_date(mtime);
+
+    let compressible = is_compressible(content_type);
+
+    let mut headers: Vec<(&'static str, String)> = vec![
+        ("Content-Type", content_type.to_string()),
+        ("Last-Modified", last_modified.clone()),
+        ("Accept-Ranges", "bytes".to_string()),
+        ("Cache-Control", "no-cache".to_string()),
+    ];
+    if compressible {
+        headers.push(("Vary", "Accept-Encoding".to_string()));
+    }
+
+    let use_gzip = compressible
+        && !has_range_header(request)
+        && header_value(request, "Accept-Encoding").is_some_and(|v| accepts_gzip(&v));
+
+    // 1. Conditional requests (If-None-Match takes precedence over If-Modified-Since)
+    let not_modified = match header_value(request, "If-None-Match") {
+        Some(value) => etag_matches(&value, &etag) || etag_matches(&value, &gzip_etag),
+        None => header_value(request, "If-Modified-Since")
+            .and_then(|v| DateTime::parse_from_rfc2822(&v).ok())
+            .is_some_and(|since| mtime <= since.timestamp()),
+    };
+
+    if not_modified {
+        headers.push(("ETag", if use_gzip { gzip_etag } else { etag }));
+        return Ok(FileResponse { status: 304, headers, body: Box::new(std::io::empty()), content_length: 0 });
+    }
+
+    // 2. Compressed full response
+    if use_gzip {
+        let contents = served_file.read_all()?;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        let compressed = encoder.write_all(&contents)
+            .and_then(|_| encoder.finish())
+            .map_err(|e| ServerError::Io { path: PathBuf::from(served_file.display_path()), source: e })?;
+
+        headers.push(("ETag", gzip_etag));
+        headers.push(("Content-Encoding", "gzip".to_string()));
+
+        let content_length = compressed.len();
+        let body: Box<dyn Read + Send> = if head_only { Box::new(std::io::empty()) } else { Box::new(Cursor::new(compressed)) };
+        return Ok(FileResponse { status: 200, headers, body, content_length });
+    }
+
+    headers.push(("ETag", etag.clone()));
+
+    // 3. Range request (ignored when If-Range no longer matches the current file)
+    let range = header_value(request, "Range")
+        .filter(|_| header_value(request, "If-Range").is_none_or(|v| v == etag || v == last_modified))
+        .and_then(|v| parse_range(&v, file_len));
+
+    match range {
+        Some(ByteRange::Satisfiable { start, end }) => {
+            let length = end - start + 1;
+            headers.push(("Content-Range", format!("bytes {}-{}/{}", start, end, file_len)));
+
+            let body: Box<dyn Read + Send> = if head_only {
+                Box::new(std::io::empty())
+            } else {
+                served_file.open_range(start, length)?
+            };
+            Ok(FileResponse { status: 206, headers, body, content_length: length as usize })
+        }
+        Some(ByteRange::Unsatisfiable) => {
+            headers.push(("Content-Range", format!("bytes */{}", file_len)));
+            Ok(FileResponse { status: 416, headers, body: Box::new(std::io::empty()), content_length: 0 })
+        }
+        None => {
+            let body: Box<dyn Read + Send> = if head_only {
+                Box::new(std::io::empty())
+            } else {
+                served_file.open_range(0, file_len)?
+            };
+            Ok(FileResponse { status: 200, headers, body, content_length: file_len as usize })
+        }
+    }
+}
+
+impl SiteSource {
+    /// Contents of the site's 404.html, if the build produced one
+    fn not_found_page(&self) -> Option<Vec<u8>> {
+        match self {
+            SiteSource::Disk(publishing_dir) => fs::read(publishing_dir.join("404.html")).ok(),
+            SiteSource::Memory(memory_site) => memory_site.files.get("404.html").map(|data| data.to_vec()),
+        }
+    }
+}
+
+impl ServedFile {
+    /// Path shown in the request log
+    fn display_path(&self) -> String {
+        match self {
+            ServedFile::Disk(path) => path.display().to_string(),
+            ServedFile::Memory { path, .. } => format!("memory:/{}", path),
+        }
+    }
+
+    fn content_type(&self) -> &'static str {
+        match self {
+            ServedFile::Disk(path) => get_content_type(path),
+            ServedFile::Memory { path, .. } => get_content_type(std::path::Path::new(path)),
+        }
+    }
+
+    /// Returns (length in bytes, modification time as unix seconds)
+    fn metadata(&self) -> Result<(u64, i64), ServerError> {
+        match self {
+            ServedFile::Disk(path) => {
+                let metadata = fs::metadata(path).map_err(|e| ServerError::Io {
+                    path: path.clone(),
+                    source: e,
+                })?;
+                let mtime = metadata.modified().ok()
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map(|d| d.as_secs() as i64)
+                    .unwrap_or(0);
+                Ok((metadata.len(), mtime))
+            }
+            ServedFile::Memory { data, modified, .. } => Ok((data.len() as u64, *modified)),
+        }
+    }
+
+    fn read_all(&self) -> Result<Vec<u8>, ServerError> {
+        match self {
+            ServedFile::Disk(path) => fs::read(path).map_err(|e| ServerError::Io { path: path.clone(), source: e }),
+            ServedFile::Memory { data, .. } => Ok(data.to_vec()),
+        }
+    }
+
+    /// Opens a reader over `length` bytes starting at `start`
+    fn open_range(&self, start: u64, length: u64) -> Result<Box<dyn Read + Send>, ServerError> {
+        match self {
+            ServedFile::Disk(path) => {
+                let mut file = fs::File::open(path)
+                    .map_err(|e| ServerError::Io { path: path.clone(), source: e })?;
+                file.seek(SeekFrom::Start(start))
+                    .map_err(|e| ServerError::Io { path: path.clone(), source: e })?;
+                Ok(Box::new(file.take(length)))
+            }
+            ServedFile::Memory { data, .. } => {
+                let start = start as usize;
+                let end = (start + length as usize).min(data.len());
+                Ok(Box::new(Cursor::new(data[start..end].to_vec())))
+            }
+        }
+    }
+}
+
+// Range requests are served uncompressed, so byte offsets refer to the file itself
+fn has_range_header(request: &tiny_http::Request) -> bool {
+    header_value(request, "Range").is_some()
+}
+
+/// Returns the value of the first request header with the given name (case-insensitive)
+fn header_value(request: &tiny_http::Request, name: &'static str) -> Option<String> {
+    request.headers().iter()
+        .find(|h| h.field.equiv(name))
+        .map(|h| h.value.as_str().trim().to_string())
+}
+
+/// Formats a unix timestamp as an HTTP date (RFC 7231 IMF-fixdate)
+fn http_date(unix_secs: i64) -> String {
+    DateTime::<Utc>::from_timestamp(unix_secs, 0)
+        .unwrap_or_default()
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string()
+}
+
+/// Checks an `If-None-Match` value (`*`, or a list of possibly weak tags) against an ETag
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match.split(',')
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+/// Checks whether `Accept-Encoding` allows gzip (`gzip;q=0` opts out, an explicit `gzip` wins over `*`)
+fn accepts_gzip(accept_encoding: &str) -> bool {
+    let mut wildcard = false;
+    for coding in accept_encoding.split(',') {
+        let mut parts = coding.split(';').map(|p| p.trim());
+        let name = parts.next().unwrap_or("");
+        let disabled = parts
+            .filter_map(|p| p.replace(' ', "").strip_prefix("q=").and_then(|q| q.parse::<f32>().ok()))
+            .any(|q| q <= 0.0);
+
+        if name.eq_ignore_ascii_case("gzip") {
+            return !disabled;
+        }
+        if name == "*" {
+            wildcard = !disabled;
+        }
+    }
+    wildcard
+}
+
+/// Text-based types worth compressing
+fn is_compressible(content_type: &str) -> bool {
+    content_type.starts_with("text/")
+        || content_type.starts_with("application/javascript")
+        || content_type.starts_with("application/json")
+        || content_type.starts_with("application/xml")
+        || content_type.starts_with("image/svg+xml")
+}
+
+/// Parses a single-range `Range: bytes=...` header
+///
+/// Returns `None` for malformed or multi-range headers, which are served as a full 200 response.
+fn parse_range(value: &str, file_len: u64) -> Option<ByteRange> {
+    let spec = value.strip_prefix("bytes=")?.trim();
+    if spec.contains(',') {
+        return None;
+    }
+
+    let (start, end) = spec.split_once('-')?;
+    let (start, end) = (start.trim(), end.trim());
+
+    let (start, end) = if start.is_empty() {
+        // Suffix range: last N bytes
+        let suffix: u64 = end.parse().ok()?;
+        if suffix == 0 || file_len == 0 {
+            return Some(ByteRange::Unsatisfiable);
+        }
+        (file_len.saturating_sub(suffix), file_len - 1)
+    } else {
+        let start: u64 = start.parse().ok()?;
+        let end: Option<u64> = if end.is_empty() { None } else { Some(end.parse().ok()?) };
+        if end.is_some_and(|end| start > end) {
+            return None;
+        }
+        if start >= file_len {
+            return Some(ByteRange::Unsatisfiable);
+        }
+        (start, end.unwrap_or(u64::MAX).min(file_len - 1))
+    };
+
+    Some(ByteRange::Satisfiable { start, end })
+}
+
 /// Validates and resolves a request path to a safe file system path
 ///
 /// Security checks:
@@ -196,10 +728,7 @@ fn validate_and_resolve_path(
     // 2. Remove leading slash
     let decoded_path = decoded_path.trim_start_matches('/');
 
-    // 3. Build candidate path
-    let candidate_path = publishing_dir.join(decoded_path);
-
-    // 4. Canonicalize publishing directory
+    // 3. Canonicalize publishing directory
     let canonical_publishing = publishing_dir
         .canonicalize()
         .map_err(|e| ServerError::Io {
@@ -207,6 +736,9 @@ fn validate_and_resolve_path(
             source: e,
         })?;
 
+    // 4. Build candidate path (under the canonical root, so the prefix check below holds)
+    let candidate_path = canonical_publishing.join(decoded_path);
+
     // 5. Try to canonicalize candidate path
     if let Ok(canonical_candidate) = candidate_path.canonicalize() {
         // Path exists - check if it's within publishing_dir
@@ -235,17 +767,22 @@ fn validate_and_resolve_path(
 ///
 /// Priority:
 /// 1. If path is a file -> return it directly
-/// 2. If path is a directory -> look for index.html
-/// 3. If no index.html -> return 404
-fn resolve_file_path(path: &std::path::Path) -> Result<PathBuf, ServerError> {
+/// 2. If path is a directory requested without a trailing slash -> redirect to the slashed URL
+///    (relative links such as `./image.png` in bundle posts resolve against the directory)
+/// 3. If path is a directory -> look for index.html
+/// 4. If no index.html -> return 404
+fn resolve_file_path(path: &std::path::Path, request_path: &str, query: Option<&str>) -> Result<ResolvedPath, ServerError> {
     if path.is_file() {
-        return Ok(path.to_path_buf());
+        return Ok(ResolvedPath::File(ServedFile::Disk(path.to_path_buf())));
     }
 
     if path.is_dir() {
         let index_path = path.join("index.html");
         if index_path.is_file() {
-            return Ok(index_path);
+            if !request_path.ends_with('/') {
+                return Ok(ResolvedPath::Redirect(directory_redirect(request_path, query)));
+            }
+            return Ok(ResolvedPath::File(ServedFile::Disk(index_path)));
         }
     }
 
@@ -254,6 +791,50 @@ fn resolve_file_path(path: &std::path::Path) -> Result<PathBuf, ServerError> {
     })
 }
 
+// "/posts/hello" + "ref=x" -> "/posts/hello/?ref=x" (the query string is kept, as on static hosts)
+fn directory_redirect(request_path: &str, query: Option<&str>) -> String {
+    match query {
+        Some(query) => format!("{}/?{}", request_path, query),
+        None => format!("{}/", request_path),
+    }
+}
+
+/// Resolves a request path against the in-memory site (same rules as `resolve_file_path`)
+fn resolve_memory_path(memory_site: &MemorySite, request_path: &str, query: Option<&str>) -> Result<ResolvedPath, ServerError> {
+    let decoded_path = urlencoding::decode(request_path)
+        .map_err(|_| ServerError::InvalidPath(request_path.to_string()))?;
+
+    let key = decoded_path.trim_start_matches('/');
+    if key.split(['/', '\\']).any(|segment| segment == "..") {
+        return Err(ServerError::PathTraversal);
+    }
+
+    let served = |path: &str| {
+        memory_site.files.get(path).map(|data| ServedFile::Memory {
+            path: path.to_string(),
+            data: Arc::clone(data),
+            modified: memory_site.built_at,
+        })
+    };
+
+    if let Some(file) = served(key) {
+        return Ok(ResolvedPath::File(file));
+    }
+
+    let dir = key.trim_end_matches('/');
+    let index_key = if dir.is_empty() { "index.html".to_string() } else { format!("{}/index.html", dir) };
+    if let Some(file) = served(&index_key) {
+        if !request_path.ends_with('/') {
+            return Ok(ResolvedPath::Redirect(directory_redirect(request_path, query)));
+        }
+        return Ok(ResolvedPath::File(file));
+    }
+
+    Err(ServerError::FileNotFound {
+        path: PathBuf::from(format!("memory:/{}", key)),
+    })
+}
+
 /// Determines Content-Type header based on file extension
 fn get_content_type(path: &std::path::Path) -> &'static str {
     match path.extension().and_then(|s| s.to_str()) {
@@ -266,12 +847,90 @@ fn get_content_type(path: &std::path::Path) -> &'static str {
         Some("jpg") | Some("jpeg") => "image/jpeg",
         Some("gif") => "image/gif",
         Some("svg") => "image/svg+xml",
+        Some("webp") => "image/webp",
+        Some("avif") => "image/avif",
         Some("ico") => "image/x-icon",
         Some("woff") => "font/woff",
         Some("woff2") => "font/woff2",
         Some("ttf") => "font/ttf",
         Some("eot") => "application/vnd.ms-fontobject",
         Some("txt") => "text/plain; charset=utf-8",
+        Some("pdf") => "application/pdf",
+        Some("mp4") => "video/mp4",
+        Some("webm") => "video/webm",
+        Some("mp3") => "audio/mpeg",
+        Some("m4a") => "audio/mp4",
+        Some("ogg") => "audio/ogg",
+        Some("wav") => "audio/wav",
         _ => "application/octet-stream",
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_range_forms() {
+        let range = |start, end| Some(ByteRange::Satisfiable { start, end });
+
+        assert_eq!(parse_range("bytes=0-99", 1000), range(0, 99));
+        assert_eq!(parse_range("bytes=900-2000", 1000), range(900, 999));    // End clamped to the file
+        assert_eq!(parse_range("bytes=500-", 1000), range(500, 999));        // Open-ended
+        assert_eq!(parse_range("bytes=-500", 1000), range(500, 999));        // Suffix: last 500 bytes
+        assert_eq!(parse_range("bytes=-5000", 1000), range(0, 999));         // Suffix longer than the file
+    }
+
+    #[test]
+    fn parse_range_unsatisfiable() {
+        // 416 Range Not Satisfiable
+        assert_eq!(parse_range("bytes=1000-", 1000), Some(ByteRange::Unsatisfiable));
+        assert_eq!(parse_range("bytes=2000-3000", 1000), Some(ByteRange::Unsatisfiable));
+        assert_eq!(parse_range("bytes=-0", 1000), Some(ByteRange::Unsatisfiable));
+        assert_eq!(parse_range("bytes=0-", 0), Some(ByteRange::Unsatisfiable));
+    }
+
+    #[test]
+    fn parse_range_falls_back_to_full_response() {
+        assert_eq!(parse_range("bytes=0-99,200-299", 1000), None);     // Multi-range
+        assert_eq!(parse_range("bytes=99-0", 1000), None);             // Reversed
+        assert_eq!(parse_range("items=0-99", 1000), None);             // Other unit
+        assert_eq!(parse_range("bytes=abc-", 1000), None);
+        assert_eq!(parse_range("bytes=-", 1000), None);
+    }
+
+    #[test]
+    fn etag_matching() {
+        let etag = "\"1a2b-3c\"";
+        assert!(etag_matches("*", etag));
+        assert!(etag_matches("\"1a2b-3c\"", etag));
+        assert!(etag_matches("W/\"1a2b-3c\"", etag));                          // Weak comparison
+        assert!(etag_matches("\"other\", W/\"1a2b-3c\"", etag));
+        assert!(!etag_matches("\"other\"", etag));
+        assert!(!etag_matches("1a2b-3c", etag));                                // Unquoted
+    }
+
+    #[test]
+    fn gzip_negotiation() {
+        assert!(accepts_gzip("gzip"));
+        assert!(accepts_gzip("deflate, GZIP;q=0.5"));
+        assert!(accepts_gzip("br, *"));
+        assert!(!accepts_gzip("gzip;q=0"));
+        assert!(!accepts_gzip("gzip; q=0.000"));
+        assert!(!accepts_gzip("gzip;q=0, *"));       // Explicit gzip wins over the wildcard
+        assert!(!accepts_gzip("*;q=0"));
+        assert!(!accepts_gzip("identity"));
+        assert!(!accepts_gzip(""));
+    }
+
+    #[test]
+    fn http_date_format() {
+        assert_eq!(http_date(0), "Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(http_date(1_784_000_000), "Tue, 14 Jul 2026 03:33:20 GMT");
+    }
+
+    #[test]
+    fn directory_redirect_keeps_query() {
+        assert_eq!(directory_redirect("/posts/hello", None), "/posts/hello/");
+        assert_eq!(directory_redirect("/posts/hello", Some("ref=x&y=1")), "/posts/hello/?ref=x&y=1");
+    }
+}
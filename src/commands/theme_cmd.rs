@@ -0,0 +1,613 @@
+// src/commands/theme_cmd.rs
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use thiserror::Error;
+
+use crate::commands::build_cmd;
+use crate::commands::create_cmd::{self, CreateError};
+use crate::config::blogconfig::{BlogConfig, BlogConfigError};
+use crate::config::theme::{ThemePackage, ThemeError};
+use crate::context::schema;
+use crate::utils::assets;
+use crate::utils::functions::{self, SiteQueries};
+use crate::utils::images;
+use crate::utils::output;
+
+#[derive(Error, Debug)]
+pub enum ThemeCmdError {
+    #[error("IO error\n  Path: {path}\n  Reason: {source}")]
+    Io { path: PathBuf, source: std::io::Error },
+
+    #[error("Blog project not found\n  Path: {path}\n  Expected: blogconfig.yaml")]
+    ProjectNotFound { path: PathBuf },
+
+    #[error("Invalid theme name\n  Name: {name}\n  Expected: a folder name without '/', '\\' or '..'")]
+    InvalidThemeName { name: String },
+
+    #[error("Theme already exists\n  Path: {path}")]
+    ThemeAlreadyExists { path: PathBuf },
+
+    #[error("Unsupported theme source\n  Path: {path}\n  Expected: a directory, .zip, .tar.gz, .tgz or .tar")]
+    UnsupportedThemeSource { path: PathBuf },
+
+    #[error("Failed to extract theme archive\n  Path: {path}\n  Reason: {reason}")]
+    ExtractThemeError { path: PathBuf, reason: String },
+
+    #[error("theme.yaml not found in theme source\n  Path: {path}\n  Expected: theme.yaml at the top level or in a single top-level folder")]
+    ThemeRootNotFound { path: PathBuf },
+
+    #[error("Theme already exists\n  Path: {path}\n  Hint: use --force to overwrite it")]
+    InstallTargetExists { path: PathBuf },
+
+    #[error("theme.name not found in blogconfig.yaml\n  Path: {path}")]
+    ThemeNameNotFound { path: PathBuf },
+
+    #[error("Theme check failed\n  Theme: {name}\n  Problems: {problems}")]
+    CheckFailed { name: String, problems: usize },
+
+    #[error(transparent)]
+    BlogConfigError(#[from] BlogConfigError),
+
+    #[error(transparent)]
+    ThemeError(#[from] ThemeError),
+
+    #[error(transparent)]
+    CreateError(#[from] CreateError),
+}
+
+#[derive(Debug)]
+pub enum ThemeAction {
+    New { name: String },
+    List,
+    Check { name: Option<String> },     // Defaults to theme.name of blogconfig.yaml
+    Install { source: PathBuf, options: InstallOptions },
+}
+
+#[derive(Debug, Default)]
+pub struct InstallOptions {
+    pub name: Option<String>,   // Folder name under themes/ (default: source name)
+    pub force: bool,            // Overwrite an installed theme
+    pub use_theme: bool,        // Set theme.name in blogconfig.yaml
+}
+
+pub fn run(action: ThemeAction, root: &str) -> Result<(), ThemeCmdError> {
+    let project_path = PathBuf::from(root);
+    let config_path = project_path.join("blogconfig.yaml");
+
+    if !config_path.is_file() {
+        return Err(ThemeCmdError::ProjectNotFound { path: project_path.clone() });
+    }
+
+    let blog_config = BlogConfig::load_from_file(&config_path)?;
+    let themes_dir = project_path.join("themes");
+
+    match action {
+        ThemeAction::New { name } => new_theme(&themes_dir, &name),
+        ThemeAction::List => list_themes(&themes_dir, &blog_config),
+        ThemeAction::Check { name } => {
+            let name = name.unwrap_or_else(|| blog_config.theme.name.clone());
+            check_theme(&themes_dir, &name, &blog_config)
+        }
+        ThemeAction::Install { source, options } => install_theme(&themes_dir, &source, &options, &config_path),
+    }
+}
+
+fn validate_theme_name(name: &str) -> Result<(), ThemeCmdError> {
+    if name.is_empty() || name == "." || name.contains(['/', '\\']) || name.contains("..") {
+        return Err(ThemeCmdError::InvalidThemeName { name: name.to_string() });
+    }
+    Ok(())
+}
+
+//------------------------------------------------------------------------------
+// theme new
+//------------------------------------------------------------------------------
+fn new_theme(themes_dir: &Path, name: &str) -> Result<(), ThemeCmdError> {
+    validate_theme_name(name)?;
+
+    let theme_dir = themes_dir.join(name);
+    if theme_dir.exists() {
+        return Err(ThemeCmdError::ThemeAlreadyExists { path: theme_dir });
+    }
+
+    output::step(&format!("Creating theme '{}'...", name));
+    create_cmd::create_theme(&theme_dir, name)?;
+
+    eprintln!();
+    output::success(&format!("Theme '{}' created successfully", name));
+    output::info(&format!("Set `theme.name: \"{}\"` in blogconfig.yaml to use it", name));
+
+    Ok(())
+}
+
+//------------------------------------------------------------------------------
+// theme list
+//------------------------------------------------------------------------------
+fn list_themes(themes_dir: &Path, blog_config: &BlogConfig) -> Result<(), ThemeCmdError> {
+    let mut theme_dirs: Vec<PathBuf> = match fs::read_dir(themes_dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_dir())
+            .collect(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(ThemeCmdError::Io { path: themes_dir.to_path_buf(), source: e }),
+    };
+    theme_dirs.sort();
+
+    if theme_dirs.is_empty() {
+        output::info(&format!("No themes found in {}", themes_dir.display()));
+        return Ok(());
+    }
+
+    output::step(&format!("Installed themes ({})", themes_dir.display()));
+    for theme_dir in &theme_dirs {
+        let dir_name = theme_dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let current = if dir_name == blog_config.theme.name { " (current)" } else { "" };
+
+        match ThemePackage::load_from_dir(theme_dir) {
+            Ok(theme_package) => {
+                let meta = &theme_package.manifest.meta;
+                let extends = match &theme_package.manifest.extends {
+                    Some(parent) => format!(", extends '{}'", parent),
+                    None => String::new(),
+                };
+                output::print_check(&format!("{}{} - {} v{} by {}{}", dir_name, current, meta.name, meta.version, meta.author, extends));
+                output::print_file(&meta.description);
+            }
+            Err(e) => {
+                let reason = e.to_string();
+                output::print_cross(&format!("{}{} - {}", dir_name, current, reason.lines().next().unwrap_or_default()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+//------------------------------------------------------------------------------
+// theme install
+//------------------------------------------------------------------------------
+#[derive(Debug, Copy, Clone)]
+enum ThemeSource {
+    Dir,
+    Zip,
+    TarGz,
+    Tar,
+}
+
+impl ThemeSource {
+    fn detect(path: &Path) -> Option<Self> {
+        if path.is_dir() {
+            return Some(ThemeSource::Dir);
+        }
+
+        let file_name = path.file_name()?.to_string_lossy().to_lowercase();
+        if file_name.ends_with(".zip") {
+            Some(ThemeSource::Zip)
+        } else if file_name.ends_with(".tar.gz") || file_name.ends_with(".tgz") {
+            Some(ThemeSource::TarGz)
+        } else if file_name.ends_with(".tar") {
+            Some(ThemeSource::Tar)
+        } else {
+            None
+        }
+    }
+}
+
+// "theme-simple-black.tar.gz" -> "theme-simple-black"
+fn source_theme_name(path: &Path) -> String {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    [".tar.gz", ".tgz", ".tar", ".zip"].iter()
+        .find_map(|ext| file_name.len().checked_sub(ext.len())
+            .filter(|&i| file_name.is_char_boundary(i) && file_name[i..].eq_ignore_ascii_case(ext))
+            .map(|i| file_name[..i].to_string()))
+        .unwrap_or(file_name)
+}
+
+/// Installs a theme from a directory or an archive (.zip, .tar.gz, .tgz, .tar) into themes/<name>/.
+///
+/// The theme is unpacked next to the installed themes (so `extends` resolves) and validated
+/// before it replaces anything.
+fn install_theme(themes_dir: &Path, source: &Path, options: &InstallOptions, config_path: &Path) -> Result<(), ThemeCmdError> {
+    let kind = ThemeSource::detect(source)
+        .ok_or_else(|| ThemeCmdError::UnsupportedThemeSource { path: source.to_path_buf() })?;
+
+    let name = options.name.clone().unwrap_or_else(|| source_theme_name(source));
+    validate_theme_name(&name)?;
+
+    let target_dir = themes_dir.join(&name);
+    if target_dir.exists() && !options.force {
+        return Err(ThemeCmdError::InstallTargetExists { path: target_dir });
+    }
+
+    output::step(&format!("Installing theme '{}' from {}...", name, source.display()));
+
+    fs::create_dir_all(themes_dir)
+        .map_err(|e| ThemeCmdError::Io { path: themes_dir.to_path_buf(), source: e })?;
+
+    let extract_dir = themes_dir.join(format!(".{}.extract", name));
+    let staged_dir = themes_dir.join(format!(".{}.staged", name));
+    for dir in [&extract_dir, &staged_dir] {
+        remove_dir_if_exists(dir)?;
+    }
+
+    let result = stage_theme(kind, source, &extract_dir, &staged_dir);
+    let result = result.and_then(|theme_package| {
+        output::print_check(&format!("{} v{} by {}", theme_package.manifest.meta.name,
+            theme_package.manifest.meta.version, theme_package.manifest.meta.author));
+
+        if target_dir.exists() {
+            output::warning(&format!("Overwriting installed theme {}", target_dir.display()));
+            remove_dir_if_exists(&target_dir)?;
+        }
+        fs::rename(&staged_dir, &target_dir)
+            .map_err(|e| ThemeCmdError::Io { path: target_dir.clone(), source: e })
+    });
+
+    // Leftovers of a failed install
+    for dir in [&extract_dir, &staged_dir] {
+        let _ = fs::remove_dir_all(dir);
+    }
+    result?;
+
+    output::print_check(&format!("{}/", target_dir.display()));
+
+    if options.use_theme {
+        set_theme_name(config_path, &name)?;
+        output::print_check(&format!("theme.name: \"{}\" ({})", name, config_path.display()));
+    }
+
+    eprintln!();
+    output::success(&format!("Theme '{}' installed successfully", name));
+    if !options.use_theme {
+        output::info(&format!("Set `theme.name: \"{}\"` in blogconfig.yaml (or use --use) to use it", name));
+    }
+
+    Ok(())
+}
+
+// Unpacks the source into `extract_dir`, moves the theme root to `staged_dir` and validates it
+fn stage_theme(kind: ThemeSource, source: &Path, extract_dir: &Path, staged_dir: &Path) -> Result<ThemePackage, ThemeCmdError> {
+    let extract_error = |reason: String| ThemeCmdError::ExtractThemeError { path: source.to_path_buf(), reason };
+
+    let open = || fs::File::open(source).map_err(|e| ThemeCmdError::Io { path: source.to_path_buf(), source: e });
+    match kind {
+        ThemeSource::Dir => copy_dir(source, extract_dir)?,
+        ThemeSource::Zip => {
+            let mut archive = zip::ZipArchive::new(open()?).map_err(|e| extract_error(e.to_string()))?;
+            archive.extract(extract_dir).map_err(|e| extract_error(e.to_string()))?;
+        }
+        ThemeSource::TarGz => {
+            tar::Archive::new(GzDecoder::new(open()?)).unpack(extract_dir)
+                .map_err(|e| extract_error(e.to_string()))?;
+        }
+        ThemeSource::Tar => {
+            tar::Archive::new(open()?).unpack(extract_dir)
+                .map_err(|e| extract_error(e.to_string()))?;
+        }
+    }
+
+    let theme_root = find_theme_root(extract_dir)
+        .ok_or_else(|| ThemeCmdError::ThemeRootNotFound { path: source.to_path_buf() })?;
+    fs::rename(&theme_root, staged_dir)
+        .map_err(|e| ThemeCmdError::Io { path: staged_dir.to_path_buf(), source: e })?;
+
+    let theme_package = ThemePackage::load_from_dir(staged_dir)?;
+    theme_package.check_generator_version()?;
+
+    Ok(theme_package)
+}
+
+// theme.yaml at the top level, or in a single top-level folder (e.g., GitHub "theme-main/" archives)
+fn find_theme_root(dir: &Path) -> Option<PathBuf> {
+    if dir.join("theme.yaml").is_file() {
+        return Some(dir.to_path_buf());
+    }
+
+    let entries: Vec<PathBuf> = fs::read_dir(dir).ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| !path.file_name().is_some_and(|n| n.to_string_lossy().starts_with('.')))
+        .collect();
+
+    match entries.as_slice() {
+        [single] if single.join("theme.yaml").is_file() => Some(single.clone()),
+        _ => None,
+    }
+}
+
+fn copy_dir(src: &Path, dst: &Path) -> Result<(), ThemeCmdError> {
+    fs::create_dir_all(dst).map_err(|e| ThemeCmdError::Io { path: dst.to_path_buf(), source: e })?;
+
+    let entries = fs::read_dir(src).map_err(|e| ThemeCmdError::Io { path: src.to_path_buf(), source: e })?;
+    for entry in entries {
+        let entry = entry.map_err(|e| ThemeCmdError::Io { path: src.to_path_buf(), source: e })?;
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+
+        if src_path.is_dir() {
+            copy_dir(&src_path, &dst_path)?;
+        } else if src_path.is_file() {
+            fs::copy(&src_path, &dst_path).map_err(|e| ThemeCmdError::Io { path: src_path.clone(), source: e })?;
+        }
+    }
+
+    Ok(())
+}
+
+fn remove_dir_if_exists(dir: &Path) -> Result<(), ThemeCmdError> {
+    if dir.exists() {
+        fs::remove_dir_all(dir).map_err(|e| ThemeCmdError::Io { path: dir.to_path_buf(), source: e })?;
+    }
+    Ok(())
+}
+
+// Rewrites the `name:` line of the `theme:` section, keeping the rest of blogconfig.yaml (comments, order) as-is
+fn set_theme_name(config_path: &Path, name: &str) -> Result<(), ThemeCmdError> {
+    let text = fs::read_to_string(config_path)
+        .map_err(|e| ThemeCmdError::Io { path: config_path.to_path_buf(), source: e })?;
+
+    let mut lines: Vec<String> = text.lines().map(|line| line.to_string()).collect();
+    let mut in_theme = false;
+    let mut child_indent: Option<String> = None;    // Indentation of the direct keys of `theme:`
+    let mut replaced = false;
+
+    for line in lines.iter_mut() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let indent = line[..line.len() - trimmed.len()].to_string();
+        if indent.is_empty() {
+            // "theme:", "theme: " or "theme:   # comment"
+            in_theme = line.split('#').next().unwrap_or_default().trim_end() == "theme:";
+            child_indent = None;
+            continue;
+        }
+        if !in_theme {
+            continue;
+        }
+
+        // Nested keys (e.g., `params: { name: ... }`) are deeper than the first key of the section
+        let child_indent = child_indent.get_or_insert_with(|| indent.clone());
+        if indent == *child_indent && trimmed.starts_with("name:") {
+            let comment = trailing_comment(trimmed);
+            *line = format!("{}name: \"{}\"{}", indent, name, comment);
+            replaced = true;
+            break;
+        }
+    }
+
+    if !replaced {
+        return Err(ThemeCmdError::ThemeNameNotFound { path: config_path.to_path_buf() });
+    }
+
+    let mut new_text = lines.join("\n");
+    if text.ends_with('\n') {
+        new_text.push('\n');
+    }
+    let write = |data: &str| fs::write(config_path, data)
+        .map_err(|e| ThemeCmdError::Io { path: config_path.to_path_buf(), source: e });
+    write(&new_text)?;
+
+    // The edit must be what the build reads; otherwise the original file is restored
+    match BlogConfig::load_from_file(config_path) {
+        Ok(blog_config) if blog_config.theme.name == name => Ok(()),
+        Ok(_) => {
+            write(&text)?;
+            Err(ThemeCmdError::ThemeNameNotFound { path: config_path.to_path_buf() })
+        }
+        Err(e) => {
+            write(&text)?;
+            Err(e.into())
+        }
+    }
+}
+
+// Inline comment of a YAML line, with the spaces before it ("name: 'a#b'  # note" -> "  # note")
+fn trailing_comment(line: &str) -> &str {
+    let mut quote: Option<char> = None;
+    let mut prev = ' ';
+    for (i, c) in line.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' && prev.is_whitespace() => return &line[line[..i].trim_end().len()..],
+            None => {}
+        }
+        prev = c;
+    }
+    ""
+}
+
+//------------------------------------------------------------------------------
+// theme check
+//------------------------------------------------------------------------------
+fn check_theme(themes_dir: &Path, name: &str, blog_config: &BlogConfig) -> Result<(), ThemeCmdError> {
+    validate_theme_name(name)?;
+
+    output::step(&format!("Checking theme '{}'...", name));
+
+    // theme.yaml, parent themes and layouts
+    let theme_package = ThemePackage::load_from_dir(&themes_dir.join(name))?;
+    theme_package.check_generator_version()?;
+    output::print_check("theme.yaml");
+
+    let mut problems = 0;
+    let mut report = |ok: bool, msg: &str| {
+        if ok {
+            output::print_check(msg);
+        } else {
+            output::print_cross(msg);
+            problems += 1;
+        }
+    };
+
+    // Templates referenced by theme.yaml
+    let manifest = &theme_package.manifest;
+    let defaults = &manifest.template_default;
+    let mut referenced: Vec<(String, &str)> = vec![
+        ("template_default.home".to_string(), defaults.home.as_str()),
+        ("template_default.list".to_string(), defaults.list.as_str()),
+        ("template_default.post".to_string(), defaults.post.as_str()),
+        ("template_default.page".to_string(), defaults.page.as_str()),
+    ];
+    if let Some(not_found) = &defaults.not_found {
+        referenced.push(("template_default.not_found".to_string(), not_found.as_str()));
+    }
+    for extra in &manifest.template_extra {
+        referenced.push((format!("template_extra ({})", extra.url), extra.file.as_str()));
+    }
+    for (layout, file) in &manifest.layouts {
+        referenced.push((format!("layouts.{}", layout), file.as_str()));
+    }
+
+    for (key, file) in &referenced {
+        let found = theme_package.find_template(file).is_some();
+        report(found, &format!("{}: {}{}", key, file, if found { "" } else { " (not found)" }));
+    }
+
+    // Sass sources
+    let theme_assets = match assets::collect_theme_assets(&theme_package) {
+        Ok(theme_assets) => {
+            report(true, &format!("assets ({} file(s))", theme_assets.len()));
+            theme_assets
+        }
+        Err(e) => {
+            report(false, &e.to_string());
+            Vec::new()
+        }
+    };
+
+    // Compile every template and check its variables against the documented context
+    let mut template_env = build_cmd::create_template_env(blog_config, &theme_package, &theme_assets);
+    functions::register_all(&mut template_env, SiteQueries::new());
+    images::register_all(&mut template_env, None);
+    let globals: BTreeSet<String> = template_env.globals().map(|(name, _)| name.to_string()).collect();
+
+    for template_name in collect_template_names(&theme_package.templates_dirs) {
+        let template = match template_env.get_template(&template_name) {
+            Ok(template) => template,
+            Err(e) => {
+                report(false, &build_cmd::template_load_error(&template_name, e).to_string());
+                continue;
+            }
+        };
+
+        let unknown: BTreeSet<String> = template.undeclared_variables(true).iter()
+            .filter(|path| !globals.contains(path.split('.').next().unwrap_or_default()))
+            .filter_map(|path| schema::unknown_variable(path))
+            .collect();
+
+        if unknown.is_empty() {
+            report(true, &template_name);
+        } else {
+            let unknown: Vec<String> = unknown.into_iter().collect();
+            report(false, &format!("{}: unknown variable(s) {}", template_name, unknown.join(", ")));
+        }
+    }
+
+    eprintln!();
+    if problems > 0 {
+        return Err(ThemeCmdError::CheckFailed { name: name.to_string(), problems });
+    }
+    output::success(&format!("Theme '{}' passed all checks", name));
+
+    Ok(())
+}
+
+// Template names ("post.html", "partials/nav.html") of the theme and its parents
+fn collect_template_names(templates_dirs: &[PathBuf]) -> BTreeSet<String> {
+    fn walk(dir: &Path, root: &Path, names: &mut BTreeSet<String>) {
+        let Ok(entries) = fs::read_dir(dir) else { return };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                walk(&path, root, names);
+            } else if path.is_file() {
+                let rel = path.strip_prefix(root).unwrap_or(&path).to_string_lossy().replace('\\', "/");
+                names.insert(rel);
+            }
+        }
+    }
+
+    let mut names = BTreeSet::new();
+    for dir in templates_dirs {
+        walk(dir, dir, &mut names);
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "\
+site:
+  name: \"Blog\"   # theme:
+  base_url: \"http://localhost:8000\"
+
+author:
+  name: \"\"
+
+theme:   # Site theme
+  # name: \"old\"
+  params:
+    name: \"Nested\"
+    colors: { name: 'x' }
+  name: 'default'   # Folder in themes/
+  pagination:
+    default: 5
+
+permalinks:
+  post: \"/posts/:slug/\"
+
+build:
+  output_dir: \"public\"
+";
+
+    fn with_config(name: &str, text: &str, test: impl FnOnce(&Path)) {
+        let dir = std::env::temp_dir().join(format!("xnbloggen-test-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("blogconfig.yaml");
+        fs::write(&config_path, text).unwrap();
+        test(&config_path);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn sets_the_theme_name_only() {
+        with_config("theme-name", CONFIG, |config_path| {
+            set_theme_name(config_path, "minimal").unwrap();
+
+            let expected = CONFIG.replace("  name: 'default'   # Folder in themes/", "  name: \"minimal\"   # Folder in themes/");
+            assert_eq!(fs::read_to_string(config_path).unwrap(), expected);
+
+            let blog_config = BlogConfig::load_from_file(config_path).unwrap();
+            assert_eq!(blog_config.theme.name, "minimal");
+            assert_eq!(blog_config.theme.params["name"], serde_yaml::Value::from("Nested"));
+        });
+    }
+
+    #[test]
+    fn missing_theme_name_is_an_error() {
+        let text = CONFIG.replace("  name: 'default'   # Folder in themes/\n", "");
+        with_config("theme-name-missing", &text, |config_path| {
+            assert!(matches!(set_theme_name(config_path, "minimal"), Err(ThemeCmdError::ThemeNameNotFound { .. })));
+            assert_eq!(fs::read_to_string(config_path).unwrap(), text);
+        });
+    }
+
+    #[test]
+    fn inline_comments() {
+        assert_eq!(trailing_comment("name: 'a'  # note"), "  # note");
+        assert_eq!(trailing_comment("name: \"a#b\" # note"), " # note");
+        assert_eq!(trailing_comment("name: 'a # b'"), "");
+        assert_eq!(trailing_comment("name: a#b"), "");
+    }
+}
@@ -1,9 +1,12 @@
 // src/config/blogconfig.rs
 
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 use serde::{Deserialize, Serialize};
 use serde_yaml;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
 
 use thiserror::Error;
 
@@ -24,6 +27,12 @@ pub struct BlogConfig {
     pub theme: ThemeConfig,
     pub permalinks: PermalinkConfig,
     pub build: BuildConfig,
+    #[serde(default)]
+    pub server: ServerConfig,
+    #[serde(default)]
+    pub images: ImagesConfig,
+    #[serde(default)]
+    pub languages: BTreeMap<String, LanguageConfig>,  // Empty = single-language site
 }
 
 // Site Configuration
@@ -37,11 +46,82 @@ pub struct SiteConfig {
     pub description: String,
     #[serde(default = "default_site_language")]
     pub language: String,
+    #[serde(default = "default_site_timezone")]
+    pub timezone: String,   // Used for front matter dates without an offset
 }
 
 fn default_site_path() -> String { "".to_string()}
 fn default_site_description() -> String { "".to_string() }
 fn default_site_language() -> String { "en".to_string() }
+fn default_site_timezone() -> String { "UTC".to_string() }
+
+// Site timezone (site.timezone)
+#[derive(Debug, Copy, Clone)]
+pub enum SiteTimezone {
+    Fixed(FixedOffset),     // e.g., "+09:00"
+    Named(Tz),              // e.g., "Asia/Seoul" (DST-aware)
+}
+
+impl SiteTimezone {
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        if let Ok(tz) = s.parse::<Tz>() {
+            return Some(SiteTimezone::Named(tz));
+        }
+
+        // "+09:00", "-0500", "+9"
+        let sign = match s.chars().next()? {
+            '+' => 1,
+            '-' => -1,
+            _ => return None,
+        };
+        let digits: String = s[1..].chars().filter(|c| *c != ':').collect();
+        let (hours, minutes) = match digits.len() {
+            1 | 2 => (digits.parse::<i32>().ok()?, 0),
+            4 => (digits[..2].parse::<i32>().ok()?, digits[2..].parse::<i32>().ok()?),
+            _ => return None,
+        };
+        FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).map(SiteTimezone::Fixed)
+    }
+
+    /// Interprets a local date/time in this timezone
+    pub fn localize(&self, local: &NaiveDateTime) -> DateTime<FixedOffset> {
+        match self {
+            SiteTimezone::Fixed(offset) => offset.from_local_datetime(local).single()
+                .unwrap_or_else(|| offset.from_utc_datetime(local)),
+            // Ambiguous (DST end) → earlier time, nonexistent (DST gap) → one hour later
+            SiteTimezone::Named(tz) => tz.from_local_datetime(local).earliest()
+                .or_else(|| tz.from_local_datetime(&(*local + Duration::hours(1))).earliest())
+                .map(|dt| dt.fixed_offset())
+                .unwrap_or_else(|| tz.from_utc_datetime(local).fixed_offset()),
+        }
+    }
+
+    /// Converts a unix timestamp (e.g., file mtime) into this timezone
+    pub fn localize_timestamp(&self, secs: i64) -> DateTime<FixedOffset> {
+        let utc = DateTime::<Utc>::from_timestamp(secs, 0).unwrap_or_default();
+        match self {
+            SiteTimezone::Fixed(offset) => utc.with_timezone(offset),
+            SiteTimezone::Named(tz) => {
+                let dt = utc.with_timezone(tz);
+                dt.with_timezone(&dt.offset().fix())
+            }
+        }
+    }
+}
+
+// Language Configuration (multilingual sites, keyed by language code)
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LanguageConfig {
+    #[serde(default)]
+    pub name: Option<String>,           // Display name for language switchers (default: language code)
+    #[serde(default)]
+    pub prefix: Option<String>,         // URL prefix (default: "" for site.language, "/<code>" otherwise)
+    #[serde(default)]
+    pub title: Option<String>,          // Overrides site.name
+    #[serde(default)]
+    pub description: Option<String>,    // Overrides site.description
+}
 
 // Author Configuration
 #[derive(Debug, Deserialize, Serialize)]
@@ -60,6 +140,34 @@ fn default_author_email() -> String { "".to_string() }
 pub struct ThemeConfig {
     #[serde(default = "default_theme_name")]
     pub name: String,
+
+    // Site-level overrides of theme.yaml settings
+    #[serde(default)]
+    pub params: BTreeMap<String, serde_yaml::Value>,    // Deep-merged over the theme's custom settings (site.theme)
+    #[serde(default)]
+    pub pagination: ThemePaginationOverride,
+    #[serde(default)]
+    pub recent_posts: RecentPostsOverride,
+    #[serde(default)]
+    pub taxonomies: BTreeMap<String, TaxonomyOverride>, // Keyed by taxonomy name (e.g., "tags")
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ThemePaginationOverride {
+    #[serde(default)]
+    pub default: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RecentPostsOverride {
+    #[serde(default)]
+    pub count: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct TaxonomyOverride {
+    #[serde(default)]
+    pub per_page: Option<usize>,
 }
 
 fn default_theme_name() -> String { "default".to_string() }
@@ -93,6 +201,16 @@ pub struct BuildConfig {
     pub sitemap: bool,
     #[serde(default = "default_robots_txt_enabled")]
     pub robots_txt: bool,
+    #[serde(default)]
+    pub git_dates: bool,    // Fill `updated` from the last git commit of each content file
+    #[serde(default)]
+    pub strict_templates: bool,     // Undefined values in templates fail the build
+    #[serde(default)]
+    pub minify: bool,       // Minify generated HTML and theme CSS/JS
+    #[serde(default)]
+    pub fingerprint: bool,  // Content hash in theme CSS/JS file names (style.1a2b3c4d.css)
+    #[serde(default)]
+    pub headers: bool,      // Write _headers with a Content-Security-Policy for the generated site
 }
 
 fn default_output_dir() -> String { "public".to_string() }
@@ -103,14 +221,209 @@ fn default_rss_max_items() -> usize { 20 }
 fn default_sitemap_enabled() -> bool { true }
 fn default_robots_txt_enabled() -> bool { true }
 
+// Server Configuration (local preview server, overridable from the command line)
+#[derive(Debug, Deserialize)]
+pub struct ServerConfig {
+    #[serde(default = "default_server_host")]
+    pub host: String,
+    #[serde(default = "default_server_port")]
+    pub port: u16,
+    #[serde(default = "default_server_workers")]
+    pub workers: usize,
+    #[serde(default)]
+    pub access_log: Option<String>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            host: default_server_host(),
+            port: default_server_port(),
+            workers: default_server_workers(),
+            access_log: None,
+        }
+    }
+}
+
+fn default_server_host() -> String { "127.0.0.1".to_string() }
+fn default_server_port() -> u16 { 8000 }
+fn default_server_workers() -> usize { 4 }
+
+// Image Configuration (markdown image markup, resized / converted variants, metadata)
+#[derive(Debug, Deserialize)]
+pub struct ImagesConfig {
+    #[serde(default)]
+    pub enabled: bool,                      // Generate resized / converted variants
+    #[serde(default = "default_image_widths")]
+    pub widths: Vec<u32>,                   // Generated widths (larger than the original are skipped)
+    #[serde(default = "default_image_formats")]
+    pub formats: Vec<ImageFormat>,          // Modern formats offered through <picture> besides the original
+    #[serde(default = "default_image_quality")]
+    pub quality: u8,                        // 1-100, for JPEG, WebP and AVIF
+    #[serde(default = "default_image_sizes")]
+    pub sizes: String,                      // `sizes` attribute of the generated markup
+    #[serde(default = "default_image_cache_dir")]
+    pub cache_dir: String,                  // Relative to the project root, kept between builds
+    #[serde(default = "default_image_lazy")]
+    pub lazy: bool,                         // loading="lazy" decoding="async" on markdown images
+    #[serde(default = "default_image_captions")]
+    pub captions: bool,                     // ![alt](src "title") alone in a paragraph -> <figure><figcaption>
+    #[serde(default)]
+    pub strip_gps: bool,                    // Remove GPS metadata from copied JPEGs
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageFormat {
+    Avif,
+    Webp,
+}
+
+impl Default for ImagesConfig {
+    fn default() -> Self {
+        ImagesConfig {
+            enabled: false,
+            widths: default_image_widths(),
+            formats: default_image_formats(),
+            quality: default_image_quality(),
+            sizes: default_image_sizes(),
+            cache_dir: default_image_cache_dir(),
+            lazy: default_image_lazy(),
+            captions: default_image_captions(),
+            strip_gps: false,
+        }
+    }
+}
+
+fn default_image_widths() -> Vec<u32> { vec![480, 960, 1600] }
+fn default_image_formats() -> Vec<ImageFormat> { vec![ImageFormat::Avif, ImageFormat::Webp] }
+fn default_image_quality() -> u8 { 80 }
+fn default_image_sizes() -> String { "100vw".to_string() }
+fn default_image_cache_dir() -> String { ".cache/images".to_string() }
+fn default_image_lazy() -> bool { true }
+fn default_image_captions() -> bool { true }
+
 impl BlogConfig {
     pub fn load_from_file(path: &Path) -> Result<Self, BlogConfigError> {
         let config_content = fs::read_to_string(path)
             .map_err(|e| BlogConfigError::LoadBlogConfigError(e.to_string()))?;
 
-        let config = serde_yaml::from_str(&config_content)
+        let config: BlogConfig = serde_yaml::from_str(&config_content)
             .map_err(|e| BlogConfigError::ParseBlogConfigError(e.to_string()))?;        
 
+        if SiteTimezone::parse(&config.site.timezone).is_none() {
+            return Err(BlogConfigError::ParseBlogConfigError(format!(
+                "invalid site.timezone '{}' (expected an IANA name such as Asia/Seoul, UTC, or an offset such as +09:00)",
+                config.site.timezone)));
+        }
+
+        if config.images.widths.is_empty() || config.images.widths.contains(&0) {
+            return Err(BlogConfigError::ParseBlogConfigError(
+                "images.widths must list at least one width greater than 0".to_string()));
+        }
+        if !(1..=100).contains(&config.images.quality) {
+            return Err(BlogConfigError::ParseBlogConfigError(format!(
+                "invalid images.quality {} (expected 1-100)", config.images.quality)));
+        }
+
         Ok(config)
     }
-}
\ No newline at end of file
+
+    /// Site timezone (validated in `load_from_file`, UTC otherwise)
+    pub fn timezone(&self) -> SiteTimezone {
+        SiteTimezone::parse(&self.site.timezone)
+            .unwrap_or(SiteTimezone::Fixed(Utc.fix()))
+    }
+
+    /// True when a `languages` block is configured
+    pub fn is_multilingual(&self) -> bool {
+        !self.languages.is_empty()
+    }
+
+    /// Language codes to build, default language (site.language) first
+    pub fn language_codes(&self) -> Vec<String> {
+        let mut codes = vec![self.site.language.clone()];
+        codes.extend(self.languages.keys()
+            .filter(|code| **code != self.site.language)
+            .cloned());
+        codes
+    }
+
+    /// URL prefix for a language (e.g., "/ko"), empty for the default language
+    pub fn language_prefix(&self, code: &str) -> String {
+        if !self.is_multilingual() {
+            return String::new();
+        }
+
+        let prefix = match self.languages.get(code).and_then(|l| l.prefix.as_ref()) {
+            Some(prefix) => prefix.clone(),
+            None if code == self.site.language => String::new(),
+            None => code.to_string(),
+        };
+
+        let prefix = prefix.trim_matches('/');
+        if prefix.is_empty() { String::new() } else { format!("/{}", prefix) }
+    }
+
+    /// Display name of a language (falls back to the language code)
+    pub fn language_name(&self, code: &str) -> String {
+        self.languages.get(code)
+            .and_then(|l| l.name.clone())
+            .unwrap_or_else(|| code.to_string())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn localized(timezone: &str, s: &str) -> String {
+        SiteTimezone::parse(timezone).unwrap().localize(&local(s)).to_rfc3339()
+    }
+
+    #[test]
+    fn timezone_forms() {
+        assert!(matches!(SiteTimezone::parse("Asia/Seoul"), Some(SiteTimezone::Named(_))));
+        assert!(matches!(SiteTimezone::parse(" UTC "), Some(SiteTimezone::Named(_))));
+
+        let offset = |s| match SiteTimezone::parse(s) {
+            Some(SiteTimezone::Fixed(offset)) => Some(offset.local_minus_utc()),
+            _ => None,
+        };
+        assert_eq!(offset("+09:00"), Some(9 * 3600));
+        assert_eq!(offset("-0530"), Some(-(5 * 3600 + 30 * 60)));
+        assert_eq!(offset("+9"), Some(9 * 3600));
+        assert_eq!(offset("+123"), None);
+        assert_eq!(offset("+25:00"), None);
+        assert_eq!(offset("Seoul"), None);
+    }
+
+    #[test]
+    fn localize_fixed_and_named() {
+        assert_eq!(localized("+09:00", "2024-01-15 09:00"), "2024-01-15T09:00:00+09:00");
+        assert_eq!(localized("Asia/Seoul", "2024-01-15 00:00"), "2024-01-15T00:00:00+09:00");
+        // Named zones pick the offset in effect on that date
+        assert_eq!(localized("Europe/Berlin", "2024-01-15 12:00"), "2024-01-15T12:00:00+01:00");
+        assert_eq!(localized("Europe/Berlin", "2024-07-15 12:00"), "2024-07-15T12:00:00+02:00");
+    }
+
+    #[test]
+    fn localize_dst_transitions() {
+        // 2024-03-10 02:30 doesn't exist in New York (clocks jump 02:00 -> 03:00): one hour later
+        assert_eq!(localized("America/New_York", "2024-03-10 02:30"), "2024-03-10T03:30:00-04:00");
+        // 2024-11-03 01:30 happens twice (01:00-02:00 repeats): the earlier one (EDT)
+        assert_eq!(localized("America/New_York", "2024-11-03 01:30"), "2024-11-03T01:30:00-04:00");
+    }
+
+    #[test]
+    fn localize_timestamp_in_zone() {
+        // 2024-01-15T00:00:00Z
+        let seoul = SiteTimezone::parse("Asia/Seoul").unwrap();
+        assert_eq!(seoul.localize_timestamp(1_705_276_800).to_rfc3339(), "2024-01-15T09:00:00+09:00");
+        let fixed = SiteTimezone::parse("-05:00").unwrap();
+        assert_eq!(fixed.localize_timestamp(1_705_276_800).to_rfc3339(), "2024-01-14T19:00:00-05:00");
+    }
+}
@@ -0,0 +1,113 @@
+// src/config/i18n.rs
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::collections::BTreeMap;
+
+use serde_yaml;
+
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum I18nError {
+    #[error("Failed to load UI strings file\n  Path: {path}\n  Reason: {source}")]
+    LoadStringsError {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Failed to parse UI strings YAML\n  Path: {path}\n  Reason: {source}")]
+    ParseStringsError {
+        path: PathBuf,
+        source: serde_yaml::Error,
+    },
+}
+
+// Built-in (English) strings used by the generator
+//   - Placeholders: {page}, {label}, {term}, {year}, {month}, {day}
+//   - Taxonomy labels can be translated with "taxonomy.<name>" keys (e.g., "taxonomy.tags": "태그")
+const DEFAULT_STRINGS: &[(&str, &str)] = &[
+    ("home", "Home"),
+    ("page", "Page {page}"),
+    ("first", "First"),
+    ("last", "Last"),
+    ("taxonomy_title", "{label}: {term}"),
+    ("archive_yearly", "Archive: {year}"),
+    ("archive_monthly", "Archive: {year}-{month}"),
+    ("archive_daily", "Archive: {year}-{month}-{day}"),
+];
+
+/// UI strings for one language.
+///
+/// Merged in order (later wins): built-in defaults, `themes/<theme>/i18n/<lang>.yaml`, `i18n/<lang>.yaml`.
+#[derive(Debug, Clone)]
+pub struct UiStrings {
+    strings: BTreeMap<String, String>,
+}
+
+impl UiStrings {
+    pub fn load(language: &str, dirs: &[&Path]) -> Result<Self, I18nError> {
+        let mut strings: BTreeMap<String, String> = DEFAULT_STRINGS.iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+        for dir in dirs {
+            if let Some(path) = find_strings_file(dir, language) {
+                strings.extend(load_strings_file(&path)?);
+            }
+        }
+
+        Ok(Self { strings })
+    }
+
+    /// Returns the string for `key`, or `key` itself if it's not defined
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.strings.get(key).map(|s| s.as_str()).unwrap_or(key)
+    }
+
+    /// Returns the string for `key` with `{name}` placeholders replaced
+    pub fn format(&self, key: &str, args: &[(&str, &str)]) -> String {
+        args.iter().fold(self.get(key).to_string(), |s, (name, value)| {
+            s.replace(&format!("{{{}}}", name), value)
+        })
+    }
+
+    /// Localized taxonomy label ("taxonomy.<name>"), falling back to theme.yaml's label
+    pub fn taxonomy_label(&self, name: &str, default: &str) -> String {
+        self.strings.get(&format!("taxonomy.{}", name))
+            .cloned()
+            .unwrap_or_else(|| default.to_string())
+    }
+
+    pub fn as_map(&self) -> &BTreeMap<String, String> {
+        &self.strings
+    }
+}
+
+// <dir>/<lang>.yaml, falling back to the primary language subtag (e.g., "ko-KR" -> "ko")
+fn find_strings_file(dir: &Path, language: &str) -> Option<PathBuf> {
+    let primary = language.split(['-', '_']).next().unwrap_or(language);
+
+    [language, primary].iter()
+        .flat_map(|lang| [dir.join(format!("{}.yaml", lang)), dir.join(format!("{}.yml", lang))])
+        .find(|path| path.is_file())
+}
+
+fn load_strings_file(path: &Path) -> Result<BTreeMap<String, String>, I18nError> {
+    let yaml = fs::read_to_string(path)
+        .map_err(|e| I18nError::LoadStringsError {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+
+    // Empty files are allowed (all defaults)
+    if yaml.trim().is_empty() {
+        return Ok(BTreeMap::new());
+    }
+
+    serde_yaml::from_str(&yaml)
+        .map_err(|e| I18nError::ParseStringsError {
+            path: path.to_path_buf(),
+            source: e,
+        })
+}
@@ -1,4 +1,5 @@
 // src/config.rs
 
 pub mod blogconfig;
-pub mod theme;
\ No newline at end of file
+pub mod theme;
+pub mod i18n;
\ No newline at end of file
@@ -9,6 +9,8 @@ use serde_yaml;
 
 use thiserror::Error;
 
+use crate::config::blogconfig::ThemeConfig;
+
 #[derive(Error, Debug)]
 pub enum ThemeError {
     #[error("Theme manifest file not found\n  Path: {path}\n  Expected: theme.yaml")]
@@ -27,13 +29,45 @@ pub enum ThemeError {
         path: PathBuf,
         source: serde_yaml::Error,
     },
+
+    #[error("Parent theme not found\n  Theme: {theme}\n  Path: {path}")]
+    ParentThemeNotFound {
+        theme: String,
+        path: PathBuf,
+    },
+
+    #[error("Theme inheritance cycle\n  Themes: {chain}")]
+    ThemeInheritanceCycle {
+        chain: String,
+    },
+
+    #[error("Theme requires a newer xnBlogGen\n  Theme: {theme}\n  Required: {required} or later\n  Current: {current}")]
+    GeneratorVersionTooOld {
+        theme: String,
+        required: String,
+        current: String,
+    },
+
+    #[error("Unknown taxonomy in blogconfig.yaml theme.taxonomies\n  Taxonomy: {name}\n  Expected: {expected}")]
+    UnknownTaxonomyOverride {
+        name: String,
+        expected: String,
+    },
+
+    #[error("Layout template not found\n  Layout: {layout}\n  Path: {path}")]
+    LayoutTemplateNotFound {
+        layout: String,
+        path: PathBuf,
+    },
 }
 
+// Directories are listed child theme first, then its parents (`extends`)
 #[derive(Debug)]
 pub struct ThemePackage {
     pub name: String,
-    pub templates_dir: PathBuf,     // themes/<theme_name>/templates/
-    pub assets_dir: PathBuf,        // themes/<theme_name>/assets/
+    pub templates_dirs: Vec<PathBuf>,   // themes/<theme_name>/templates/
+    pub assets_dirs: Vec<PathBuf>,      // themes/<theme_name>/assets/
+    pub i18n_dirs: Vec<PathBuf>,        // themes/<theme_name>/i18n/
 
     pub manifest: ThemeManifest,
 }
@@ -41,11 +75,17 @@ pub struct ThemePackage {
 #[derive(Debug, Deserialize)]
 pub struct ThemeManifest {
     pub meta: ThemeMeta,
+
+    #[serde(default)]
+    pub extends: Option<String>,    // Parent theme (directory name under themes/)
     pub template_default: ThemeTemplateDefault,
 
     #[serde(default)]
     pub template_extra: Vec<ThemeTemplateExtra>,
 
+    #[serde(default)]
+    pub layouts: BTreeMap<String, String>,     // Layout name -> template file (front matter `layout:`)
+
     #[serde(default)]
     pub pagination: ThemePagination,
 
@@ -71,6 +111,8 @@ pub struct ThemeMeta {
     pub author: String,
     #[serde(default = "default_theme_description")]
     pub description: String,
+    #[serde(default)]
+    pub min_generator_version: Option<String>,  // e.g., "0.2.0" - checked against the running xnBlogGen
 }
 
 fn default_theme_version() -> String { "0.1.0".to_string() }
@@ -87,6 +129,8 @@ pub struct ThemeTemplateDefault {
     pub post: String,
     #[serde(default = "default_theme_page")]
     pub page: String,
+    #[serde(default)]
+    pub not_found: Option<String>,  // e.g., "404.html" - rendered to /404.html
 }
 
 fn default_theme_home() -> String { "home.html".to_string() }
@@ -180,33 +224,206 @@ impl Default for RecentPostsConfig {
 fn default_recent_posts_count() -> usize { 10 }
 
 
+/// Merges `overlay` into `base`: mappings are merged key by key, other values (including lists) are replaced
+pub fn deep_merge(base: &mut serde_yaml::Value, overlay: serde_yaml::Value) {
+    match (base, overlay) {
+        (serde_yaml::Value::Mapping(base), serde_yaml::Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => deep_merge(existing, value),
+                    None => { base.insert(key, value); }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+fn read_manifest_value(theme_dir: &Path) -> Result<serde_yaml::Value, ThemeError> {
+    let manifest_path = theme_dir.join("theme.yaml");
+    if !manifest_path.is_file() {
+        return Err(ThemeError::ThemeManifestNotFound { path: theme_dir.to_path_buf() });
+    }
+
+    let yaml = fs::read_to_string(&manifest_path)
+        .map_err(|e| ThemeError::LoadThemeManifestError {
+            path: manifest_path.clone(),
+            source: e,
+        })?;
+
+    serde_yaml::from_str(&yaml)
+        .map_err(|e| ThemeError::ParseThemeManifestError {
+            path: manifest_path,
+            source: e,
+        })
+}
+
 impl ThemePackage {
+    /// Loads a theme and its parents (`extends: <theme>`).
+    ///   - Manifests are deep-merged (child settings win)
+    ///   - Templates are looked up in the child theme first, assets and i18n files of the child win
     pub fn load_from_dir(theme_dir: &Path) -> Result<Self, ThemeError> {
-        let manifest_path = theme_dir.join("theme.yaml");
-        if !manifest_path.is_file() {
-            return Err(ThemeError::ThemeManifestNotFound { path: theme_dir.to_path_buf() });
+        let mut theme_dirs = vec![theme_dir.to_path_buf()];
+        let mut values = vec![read_manifest_value(theme_dir)?];
+
+        while let Some(parent) = values.last().and_then(|v| v.get("extends")).and_then(|v| v.as_str()) {
+            let parent_dir = theme_dir.parent().unwrap_or(Path::new(".")).join(parent);
+            if theme_dirs.contains(&parent_dir) {
+                let mut chain: Vec<String> = theme_dirs.iter()
+                    .map(|dir| dir.file_name().unwrap_or_default().to_string_lossy().to_string())
+                    .collect();
+                chain.push(parent.to_string());
+                return Err(ThemeError::ThemeInheritanceCycle { chain: chain.join(" -> ") });
+            }
+            if !parent_dir.join("theme.yaml").is_file() {
+                return Err(ThemeError::ParentThemeNotFound { theme: parent.to_string(), path: parent_dir });
+            }
+
+            values.push(read_manifest_value(&parent_dir)?);
+            theme_dirs.push(parent_dir);
         }
 
-        let yaml = fs::read_to_string(&manifest_path)
-            .map_err(|e| ThemeError::LoadThemeManifestError {
-                path: manifest_path.clone(),
-                source: e,
-            })?;
+        // Root theme first, each child on top
+        let mut merged = values.pop().unwrap_or_default();
+        while let Some(child) = values.pop() {
+            deep_merge(&mut merged, child);
+        }
 
-        let manifest: ThemeManifest = serde_yaml::from_str(&yaml)
+        let manifest: ThemeManifest = serde_yaml::from_value(merged)
             .map_err(|e| ThemeError::ParseThemeManifestError {
-                path: manifest_path.clone(),
+                path: theme_dir.join("theme.yaml"),
                 source: e,
             })?;
 
-        let templates_dir = theme_dir.join("templates");
-        let assets_dir = theme_dir.join("assets");
-
-        Ok(Self {
+        let theme_package = Self {
             name: manifest.meta.name.clone(),
-            templates_dir,
-            assets_dir,
+            templates_dirs: theme_dirs.iter().map(|dir| dir.join("templates")).collect(),
+            assets_dirs: theme_dirs.iter().map(|dir| dir.join("assets")).collect(),
+            i18n_dirs: theme_dirs.iter().map(|dir| dir.join("i18n")).collect(),
             manifest,
-        })
+        };
+
+        for (layout, file) in &theme_package.manifest.layouts {
+            if theme_package.find_template(file).is_none() {
+                return Err(ThemeError::LayoutTemplateNotFound {
+                    layout: layout.clone(),
+                    path: theme_package.templates_dirs[0].join(file),
+                });
+            }
+        }
+
+        Ok(theme_package)
+    }
+
+    /// Applies the site-level overrides of `blogconfig.yaml` (`theme.params`, `pagination`, `recent_posts`, `taxonomies`)
+    pub fn apply_site_overrides(&mut self, theme_config: &ThemeConfig) -> Result<(), ThemeError> {
+        let manifest = &mut self.manifest;
+
+        for (key, value) in &theme_config.params {
+            match manifest.others.get_mut(key) {
+                Some(existing) => deep_merge(existing, value.clone()),
+                None => { manifest.others.insert(key.clone(), value.clone()); }
+            }
+        }
+
+        if let Some(per_page) = theme_config.pagination.default {
+            manifest.pagination.default = per_page;
+        }
+        if let Some(count) = theme_config.recent_posts.count {
+            manifest.recent_posts.count = count;
+        }
+
+        for (name, taxonomy_override) in &theme_config.taxonomies {
+            let Some(taxonomy) = manifest.taxonomies.iter_mut().find(|t| t.name == *name) else {
+                let names: Vec<&str> = manifest.taxonomies.iter().map(|t| t.name.as_str()).collect();
+                return Err(ThemeError::UnknownTaxonomyOverride {
+                    name: name.clone(),
+                    expected: if names.is_empty() { "none (no taxonomies in theme.yaml)".to_string() } else { names.join(", ") },
+                });
+            };
+            if let Some(per_page) = taxonomy_override.per_page {
+                taxonomy.per_page = per_page;
+            }
+        }
+
+        Ok(())
     }
-}
\ No newline at end of file
+
+    /// Fails if the theme requires a newer xnBlogGen (`meta.min_generator_version`)
+    pub fn check_generator_version(&self) -> Result<(), ThemeError> {
+        let current = env!("CARGO_PKG_VERSION");
+        match &self.manifest.meta.min_generator_version {
+            Some(required) if !version_at_least(current, required) => {
+                Err(ThemeError::GeneratorVersionTooOld {
+                    theme: self.name.clone(),
+                    required: required.clone(),
+                    current: current.to_string(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Path of a template file, looked up in the child theme first
+    pub fn find_template(&self, name: &str) -> Option<PathBuf> {
+        if name.split(['/', '\\']).any(|part| part == "..") {
+            return None;
+        }
+        self.templates_dirs.iter()
+            .map(|dir| dir.join(name))
+            .find(|path| path.is_file())
+    }
+}
+
+// "1.2.3" -> ([1, 2, 3], true) ("v" prefix and build metadata are ignored, missing parts are 0).
+// The flag is false for pre-releases ("1.2.0-beta"), which come before the release.
+fn version_parts(version: &str) -> ([u64; 3], bool) {
+    let version = version.trim().trim_start_matches('v');
+    let version = version.split('+').next().unwrap_or_default();
+    let (version, is_release) = match version.split_once('-') {
+        Some((version, _)) => (version, false),
+        None => (version, true),
+    };
+
+    let mut parts = [0; 3];
+    for (part, value) in parts.iter_mut().zip(version.split('.')) {
+        *part = value.parse().unwrap_or(0);
+    }
+    (parts, is_release)
+}
+
+// Whether `version` satisfies a minimum version (pre-release labels aren't compared with each other)
+fn version_at_least(version: &str, required: &str) -> bool {
+    let (parts, is_release) = version_parts(version);
+    let (required_parts, required_is_release) = version_parts(required);
+    parts > required_parts || (parts == required_parts && (is_release || !required_is_release))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_forms() {
+        assert_eq!(version_parts("1.2.3"), ([1, 2, 3], true));
+        assert_eq!(version_parts(" v0.2 "), ([0, 2, 0], true));
+        assert_eq!(version_parts("1.2.0-beta.1"), ([1, 2, 0], false));
+        assert_eq!(version_parts("1.2.0+build.5"), ([1, 2, 0], true));
+        assert_eq!(version_parts("1.x"), ([1, 0, 0], true));
+    }
+
+    #[test]
+    fn minimum_versions() {
+        assert!(version_at_least("1.2.0", "1.2.0"));
+        assert!(version_at_least("1.10.0", "1.9.9"));
+        assert!(!version_at_least("1.2.0", "1.2.1"));
+        assert!(!version_at_least("0.9.0", "v1"));
+
+        // A pre-release comes before its release, but after the previous version
+        assert!(version_at_least("1.2.0", "1.2.0-beta"));
+        assert!(!version_at_least("1.2.0-beta", "1.2.0"));
+        assert!(version_at_least("1.2.0-beta", "1.1.9"));
+        assert!(version_at_least("1.2.0-beta", "1.2.0-alpha"));
+        assert!(version_at_least("1.2.0+build", "1.2.0"));
+    }
+}
@@ -6,6 +6,10 @@ use std::path::{Path, PathBuf};
 use thiserror::Error;
 use serde_yaml;
 
+use chrono::{DateTime, FixedOffset, NaiveDate};
+
+use crate::config::blogconfig::SiteTimezone;
+
 use super::{ContentKind, SourceId, SourceMeta, ContentFrontMatter, ContentSource};
 use super::MarkdownBody;
 
@@ -22,9 +26,22 @@ pub enum ContentLoaderError {
 
     #[error("Missing required front matter field\n  Path: {path}\n  Field: {field}")]
     MissingField { path: PathBuf, field: &'static str },
+
+    #[error("Unknown content language\n  Path: {path}\n  Language: {language}\n  Expected: one of the codes in 'languages' ({expected})")]
+    UnknownLanguage { path: PathBuf, language: String, expected: String },
+}
+
+// Site settings used while loading contents
+//   - default_language: site.language
+//   - languages: configured language codes (empty = single-language site, no suffix detection)
+//   - timezone: site.timezone (front matter dates without an offset)
+pub struct ContentLoadOptions<'a> {
+    pub default_language: &'a str,
+    pub languages: &'a [String],
+    pub timezone: SiteTimezone,
 }
 
-pub fn load_all_contents(content_root: &Path) -> Result<Vec<ContentSource>, ContentLoaderError> {
+pub fn load_all_contents(content_root: &Path, options: &ContentLoadOptions) -> Result<Vec<ContentSource>, ContentLoaderError> {
     let mut out = Vec::new();
 
     let content_data = [
@@ -33,16 +50,16 @@ pub fn load_all_contents(content_root: &Path) -> Result<Vec<ContentSource>, Cont
     ];
 
     for (content_dir, content_kind) in &content_data {
-        let (md_files, image_files) = list_content_files(content_dir)?;
+        let (md_files, image_files) = list_content_files(content_dir, options.languages)?;
         for md_file in md_files {
-            out.push(load_content_file(&md_file, content_root, *content_kind, &image_files)?);
+            out.push(load_content_file(&md_file, content_root, *content_kind, &image_files, options)?);
         }
     }
 
     Ok(out)
 }
 
-fn list_content_files(dir: &Path) -> Result<(Vec<PathBuf>, Vec<PathBuf>), ContentLoaderError> {
+fn list_content_files(dir: &Path, languages: &[String]) -> Result<(Vec<PathBuf>, Vec<PathBuf>), ContentLoaderError> {
     let mut md_files = Vec::new();
     let mut image_files = Vec::new();
 
@@ -57,9 +74,10 @@ fn list_content_files(dir: &Path) -> Result<(Vec<PathBuf>, Vec<PathBuf>), Conten
         let path = entry.path();
 
         if path.is_dir() {
-            let index_md = path.join("index.md");
-            if index_md.is_file() {
-                md_files.push(index_md);
+            // Bundle: index.md plus translations such as index.ko.md (configured languages only)
+            let index_mds = list_bundle_index_files(&path, languages)?;
+            if !index_mds.is_empty() {
+                md_files.extend(index_mds);
 
                 for sub_entry in fs::read_dir(&path)
                     .map_err(|e| ContentLoaderError::Io { path: path.to_path_buf(), source: e })? {
@@ -92,6 +110,29 @@ fn list_content_files(dir: &Path) -> Result<(Vec<PathBuf>, Vec<PathBuf>), Conten
     Ok((md_files, image_files))
 }
 
+fn list_bundle_index_files(dir: &Path, languages: &[String]) -> Result<Vec<PathBuf>, ContentLoaderError> {
+    let mut index_files = Vec::new();
+
+    for entry in fs::read_dir(dir)
+        .map_err(|e| ContentLoaderError::Io { path: dir.to_path_buf(), source: e })? {
+
+        let entry = entry.map_err(|e| ContentLoaderError::Io { path: dir.to_path_buf(), source: e })?;
+        let path = entry.path();
+        if !path.is_file() { continue; }
+
+        let is_index = path.file_name()
+            .and_then(|s| s.to_str())
+            .is_some_and(|name| name == "index.md" || name.strip_prefix("index.")
+                .and_then(|rest| rest.strip_suffix(".md"))
+                .is_some_and(|code| languages.iter().any(|language| language == code)));
+        if is_index {
+            index_files.push(path);
+        }
+    }
+
+    Ok(index_files)
+}
+
 fn read_file(path: &Path) -> Result<String, ContentLoaderError> {
     fs::read_to_string(path).map_err(|e| ContentLoaderError::Io { path: path.to_path_buf(), source: e })
 }
@@ -145,7 +186,21 @@ fn make_source_id(content_root: &Path, file_path: &Path) -> SourceId {
     SourceId(s)
 }
 
-fn load_content_file(path: &Path, content_root: &Path, kind: ContentKind, all_images: &[PathBuf]) -> Result<ContentSource, ContentLoaderError> {
+// Split a language suffix off a file stem (e.g., "post.ko" -> ("post", Some("ko")))
+fn split_language_suffix<'a>(stem: &'a str, codes: &[String]) -> (&'a str, Option<&'a str>) {
+    match stem.rsplit_once('.') {
+        Some((base, suffix)) if codes.iter().any(|code| code == suffix) => (base, Some(suffix)),
+        _ => (stem, None),
+    }
+}
+
+fn load_content_file(
+    path: &Path,
+    content_root: &Path,
+    kind: ContentKind,
+    all_images: &[PathBuf],
+    options: &ContentLoadOptions,
+) -> Result<ContentSource, ContentLoaderError> {
     let raw = read_file(path)?;
     let (yaml, markdown) = split_front_matter(&raw, path)?;
 
@@ -162,34 +217,99 @@ fn load_content_file(path: &Path, content_root: &Path, kind: ContentKind, all_im
         source_mtime_unix: file_mtime_unix(path),
     };
 
-    let images = filter_content_images(path, &fm, all_images);
+    // Language: front matter > filename suffix (post.ko.md, index.ko.md) > site.language
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    let (base_stem, suffix_language) = split_language_suffix(stem, options.languages);
+
+    let language = fm.language.clone()
+        .or_else(|| suffix_language.map(|s| s.to_string()))
+        .unwrap_or_else(|| options.default_language.to_string());
+
+    if !options.languages.is_empty() && !options.languages.contains(&language) {
+        return Err(ContentLoaderError::UnknownLanguage {
+            path: path.to_path_buf(),
+            language,
+            expected: options.languages.join(", "),
+        });
+    }
+
+    // Date: front matter > YYYY-MM-DD- filename prefix (folder name for bundles) > file mtime
+    let date = match &fm.date {
+        Some(date) => date.resolve(&options.timezone),
+        None => infer_date(path, base_stem, &options.timezone, meta.source_mtime_unix),
+    };
+    let updated = fm.updated.as_ref().map(|d| d.resolve(&options.timezone));
+
+    // Translation key: front matter > source id without the language suffix (e.g., "posts/hello")
+    let translation_key = fm.translation_key.clone().unwrap_or_else(|| {
+        let id = &meta.id.0;
+        match suffix_language {
+            Some(suffix) => id.strip_suffix(&format!(".{}", suffix)).unwrap_or(id).to_string(),
+            None => id.clone(),
+        }
+    });
+
+    let images = filter_content_images(path, base_stem, &fm, &date, all_images);
+
+    Ok(ContentSource {
+        kind,
+        meta,
+        front_matter: fm,
+        body: MarkdownBody { markdown },
+        images,
+        date,
+        updated,
+        git: None,
+        language,
+        translation_key,
+    })
+}
+
+fn infer_date(path: &Path, base_stem: &str, timezone: &SiteTimezone, mtime_unix: Option<i64>) -> DateTime<FixedOffset> {
+    // Bundles (posts/2024-01-15-my-post/index.md) use the folder name
+    let name = if base_stem == "index" {
+        path.parent()
+            .and_then(|p| p.file_name())
+            .and_then(|s| s.to_str())
+            .unwrap_or(base_stem)
+    } else {
+        base_stem
+    };
+
+    let prefix_date = name.get(..10)
+        .filter(|_| name.len() == 10 || name[10..].starts_with('-'))
+        .and_then(|prefix| NaiveDate::parse_from_str(prefix, "%Y-%m-%d").ok())
+        .and_then(|nd| nd.and_hms_opt(0, 0, 0));
 
-    Ok(ContentSource { kind, meta, front_matter: fm, body: MarkdownBody { markdown }, images } )
+    match (prefix_date, mtime_unix) {
+        (Some(ndt), _) => timezone.localize(&ndt),
+        (None, Some(mtime)) => timezone.localize_timestamp(mtime),
+        (None, None) => timezone.localize_timestamp(chrono::Utc::now().timestamp()),
+    }
 }
 
-fn filter_content_images(md_path: &Path, front_matter: &ContentFrontMatter, all_images: &[PathBuf]) -> Vec<PathBuf> {
+fn filter_content_images(
+    md_path: &Path,
+    base_stem: &str,
+    front_matter: &ContentFrontMatter,
+    date: &DateTime<FixedOffset>,
+    all_images: &[PathBuf],
+) -> Vec<PathBuf> {
     // Pattern 1: Folder Images (e.g., content/posts/my-post/index.md -> content/posts/my-post/*)
-    if md_path.file_name().and_then(|s| s.to_str()) == Some("index.md") {
-        if let Some(parent_dir) = md_path.parent() {
-            return all_images.iter()
-                .filter(|img_path| img_path.parent() == Some(parent_dir))
-                .cloned()
-                .collect();
-        }
+    if base_stem == "index" && let Some(parent_dir) = md_path.parent() {
+        return all_images.iter()
+            .filter(|img_path| img_path.parent() == Some(parent_dir))
+            .cloned()
+            .collect();
     }
 
     // Pattern 2: Flattened Images with Slug(optional Date) (e.g., content/posts/2023-10-01-my-post.md -> content/posts/my-post-* or content/posts/2023-10-01-my-post-*)
-    let date = front_matter.date.format("%Y-%m-%d").to_string();
+    let date = date.format("%Y-%m-%d").to_string();
     let title_slug = if let Some(slug) = &front_matter.slug {
         slug.clone()
     } else {
-        md_path.file_stem()
-            .and_then(|s| s.to_str().map(|s| s.to_lowercase()))
-            .map(|s| {
-                let s = s.trim_start_matches(&date).trim_start_matches('-');
-                s.to_string()
-            })
-            .unwrap_or_default()
+        let s = base_stem.to_lowercase();
+        s.trim_start_matches(&date).trim_start_matches('-').to_string()
     };
 
     let slug_prefix = format!("{}-", title_slug);
@@ -209,4 +329,110 @@ fn filter_content_images(md_path: &Path, front_matter: &ContentFrontMatter, all_
 
 fn is_image_ext(ext: &str) -> bool {
     matches!(ext, "png" | "jpg" | "jpeg" | "gif" | "webp" | "svg" | "avif" | "bmp" | "ico" | "tiff" | "tif")
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(codes: &[&str]) -> Vec<String> {
+        codes.iter().map(|code| code.to_string()).collect()
+    }
+
+    // Empty scratch directory under the system temp dir, removed by the caller
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("xnbloggen-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn language_suffix_of_configured_codes() {
+        let languages = codes(&["en", "ko"]);
+        assert_eq!(split_language_suffix("post.ko", &languages), ("post", Some("ko")));
+        assert_eq!(split_language_suffix("index.en", &languages), ("index", Some("en")));
+        assert_eq!(split_language_suffix("2024-01-15-trip.ko", &languages), ("2024-01-15-trip", Some("ko")));
+    }
+
+    #[test]
+    fn language_suffix_ignores_other_dots() {
+        let languages = codes(&["en", "ko"]);
+        assert_eq!(split_language_suffix("v1.2", &languages), ("v1.2", None));
+        assert_eq!(split_language_suffix("post.ja", &languages), ("post.ja", None));    // Not configured
+        assert_eq!(split_language_suffix("post", &languages), ("post", None));
+        // Single-language sites: no suffix detection at all
+        assert_eq!(split_language_suffix("post.ko", &[]), ("post.ko", None));
+    }
+
+    #[test]
+    fn bundle_index_files() {
+        let dir = scratch_dir("bundle");
+        for name in ["index.md", "index.ko.md", "index.ja.md", "index.old.md", "v1.2.md", "notes.md"] {
+            fs::write(dir.join(name), "---\ntitle: x\n---\n").unwrap();
+        }
+        let names = |languages: &[String]| {
+            let mut names: Vec<String> = list_bundle_index_files(&dir, languages).unwrap().iter()
+                .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+                .collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(names(&codes(&["en", "ko"])), ["index.ko.md", "index.md"]);
+        assert_eq!(names(&[]), ["index.md"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn inferred_dates() {
+        let seoul = SiteTimezone::parse("Asia/Seoul").unwrap();
+        let mtime = Some(1_705_276_800);    // 2024-01-15T00:00:00Z
+        let infer = |path: &str, stem: &str, mtime| infer_date(Path::new(path), stem, &seoul, mtime).to_rfc3339();
+
+        // Filename prefix wins over mtime, at midnight in the site timezone
+        assert_eq!(infer("posts/2023-05-01-hello.md", "2023-05-01-hello", mtime), "2023-05-01T00:00:00+09:00");
+        assert_eq!(infer("posts/2023-05-01.md", "2023-05-01", mtime), "2023-05-01T00:00:00+09:00");
+        // Bundles use the folder name
+        assert_eq!(infer("posts/2023-05-01-trip/index.md", "index", mtime), "2023-05-01T00:00:00+09:00");
+        // No (valid) prefix: mtime in the site timezone
+        assert_eq!(infer("posts/hello.md", "hello", mtime), "2024-01-15T09:00:00+09:00");
+        assert_eq!(infer("posts/2023-05-01hello.md", "2023-05-01hello", mtime), "2024-01-15T09:00:00+09:00");
+        assert_eq!(infer("posts/2023-13-01-x.md", "2023-13-01-x", mtime), "2024-01-15T09:00:00+09:00");
+    }
+
+    #[test]
+    fn load_language_from_file_names() {
+        let root = scratch_dir("languages");
+        let posts = root.join("posts");
+        fs::create_dir_all(posts.join("2024-01-15-trip")).unwrap();
+        for name in ["post.md", "post.ko.md", "v1.2.md", "2024-01-15-trip/index.md", "2024-01-15-trip/index.en.md"] {
+            fs::write(posts.join(name), "---\ntitle: x\n---\n").unwrap();
+        }
+
+        let languages = codes(&["ko", "en"]);
+        let options = ContentLoadOptions {
+            default_language: "ko",
+            languages: &languages,
+            timezone: SiteTimezone::parse("+09:00").unwrap(),
+        };
+        let contents = load_all_contents(&root, &options).unwrap();
+        let mut loaded: Vec<(String, &str, &str)> = contents.iter()
+            .map(|c| {
+                let path = c.meta.source_path.strip_prefix(&posts).unwrap().to_string_lossy().replace('\\', "/");
+                (path, c.language.as_str(), c.translation_key.as_str())
+            })
+            .collect();
+        loaded.sort();
+
+        assert_eq!(loaded, [
+            ("2024-01-15-trip/index.en.md".to_string(), "en", "posts/2024-01-15-trip/index"),
+            ("2024-01-15-trip/index.md".to_string(), "ko", "posts/2024-01-15-trip/index"),
+            ("post.ko.md".to_string(), "ko", "posts/post"),
+            ("post.md".to_string(), "ko", "posts/post"),
+            ("v1.2.md".to_string(), "ko", "posts/v1.2"),
+        ]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -3,11 +3,15 @@
 use std::path::PathBuf;
 use std::collections::BTreeMap;
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_yaml;
-use chrono::{DateTime, FixedOffset};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime};
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
+use crate::config::blogconfig::SiteTimezone;
+
+use super::git_history::GitInfo;
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum ContentKind {
     Post,
@@ -27,6 +31,65 @@ pub struct SourceMeta {
     pub source_mtime_unix: Option<i64>,
 }
 
+// Front matter date
+//   - With offset: "2024-01-15T09:00:00+09:00", "2024-01-15 09:00:00 +0900" (Jekyll)
+//   - Without offset (interpreted in site.timezone): "2024-01-15", "2024-01-15 09:00", "2024-01-15T09:00:00"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrontMatterDate {
+    Offset(DateTime<FixedOffset>),
+    Local(NaiveDateTime),
+}
+
+impl FrontMatterDate {
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+
+        if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+            return Some(FrontMatterDate::Offset(dt));
+        }
+        for fmt in ["%Y-%m-%d %H:%M:%S %z", "%Y-%m-%d %H:%M:%S%:z", "%Y-%m-%d %H:%M %z", "%Y-%m-%dT%H:%M%:z"] {
+            if let Ok(dt) = DateTime::parse_from_str(s, fmt) {
+                return Some(FrontMatterDate::Offset(dt));
+            }
+        }
+
+        for fmt in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"] {
+            if let Ok(ndt) = NaiveDateTime::parse_from_str(s, fmt) {
+                return Some(FrontMatterDate::Local(ndt));
+            }
+        }
+
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()
+            .and_then(|nd| nd.and_hms_opt(0, 0, 0))
+            .map(FrontMatterDate::Local)
+    }
+
+    pub fn resolve(&self, timezone: &SiteTimezone) -> DateTime<FixedOffset> {
+        match self {
+            FrontMatterDate::Offset(dt) => *dt,
+            FrontMatterDate::Local(ndt) => timezone.localize(ndt),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for FrontMatterDate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        FrontMatterDate::parse(&s).ok_or_else(|| serde::de::Error::custom(format!(
+            "invalid date '{}' (expected e.g. 2024-01-15, 2024-01-15 09:00 or 2024-01-15T09:00:00+09:00)", s
+        )))
+    }
+}
+
+impl Serialize for FrontMatterDate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            FrontMatterDate::Offset(dt) => serializer.serialize_str(&dt.to_rfc3339()),
+            FrontMatterDate::Local(ndt) => serializer.serialize_str(&ndt.format("%Y-%m-%dT%H:%M:%S").to_string()),
+        }
+    }
+}
+
 // Content Front Matter Structures
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ContentFrontMatter {
@@ -36,10 +99,10 @@ pub struct ContentFrontMatter {
     pub slug: Option<String>,
 
     #[serde(default)]
-    pub date: DateTime<FixedOffset>,
+    pub date: Option<FrontMatterDate>,      // Inferred from the filename or mtime if absent
 
     #[serde(default)]
-    pub updated: Option<DateTime<FixedOffset>>,    
+    pub updated: Option<FrontMatterDate>,    
 
     #[serde(default)]
     pub draft: bool,
@@ -50,6 +113,15 @@ pub struct ContentFrontMatter {
     #[serde(default)]
     pub language: Option<String>,
 
+    #[serde(default)]
+    pub translation_key: Option<String>,    // Links translations of the same content
+
+    #[serde(default)]
+    pub template: Option<String>,           // Theme template file (e.g., "landing.html")
+
+    #[serde(default)]
+    pub layout: Option<String>,             // Named layout from theme.yaml `layouts`
+
     #[serde(default)]
     pub extra: BTreeMap<String, serde_yaml::Value>,
 
@@ -78,4 +150,51 @@ pub struct ContentSource {
 
     #[serde(default)]
     pub images: Vec<PathBuf>,
+
+    // Resolved dates (date: front matter > YYYY-MM-DD- filename prefix > file mtime)
+    pub date: DateTime<FixedOffset>,
+    pub updated: Option<DateTime<FixedOffset>>,
+
+    // Last commit touching the source file (build.git_dates)
+    #[serde(default)]
+    pub git: Option<GitInfo>,
+
+    // Resolved language (front matter > filename suffix > site.language)
+    pub language: String,
+
+    // Resolved translation key (front matter > source id without language suffix)
+    pub translation_key: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolved(yaml_date: &str, timezone: &str) -> String {
+        let fm: ContentFrontMatter = serde_yaml::from_str(&format!("title: x\ndate: {}\n", yaml_date)).unwrap();
+        fm.date.unwrap().resolve(&SiteTimezone::parse(timezone).unwrap()).to_rfc3339()
+    }
+
+    #[test]
+    fn local_dates_use_the_site_timezone() {
+        assert_eq!(resolved("2024-01-15", "Asia/Seoul"), "2024-01-15T00:00:00+09:00");
+        assert_eq!(resolved("2024-01-15 09:00", "Asia/Seoul"), "2024-01-15T09:00:00+09:00");
+        assert_eq!(resolved("\"2024-01-15T09:00:30\"", "-05:00"), "2024-01-15T09:00:30-05:00");
+        assert_eq!(resolved("2024-01-15", "UTC"), "2024-01-15T00:00:00+00:00");
+    }
+
+    #[test]
+    fn offset_dates_are_kept() {
+        assert_eq!(resolved("2024-01-15T09:00:00+09:00", "America/New_York"), "2024-01-15T09:00:00+09:00");
+        assert_eq!(resolved("2024-01-15T00:00:00Z", "Asia/Seoul"), "2024-01-15T00:00:00+00:00");
+        assert_eq!(resolved("2024-01-15 09:00:00 +0100", "Asia/Seoul"), "2024-01-15T09:00:00+01:00");
+    }
+
+    #[test]
+    fn invalid_dates_are_rejected() {
+        for date in ["2024-13-01", "15.01.2024", "yesterday"] {
+            let result = serde_yaml::from_str::<ContentFrontMatter>(&format!("title: x\ndate: {}\n", date));
+            assert!(result.is_err(), "{}", date);
+        }
+    }
 }
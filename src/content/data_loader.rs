@@ -0,0 +1,145 @@
+// src/content/data_loader.rs
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::collections::BTreeMap;
+
+use serde_yaml::{Mapping, Value};
+use thiserror::Error;
+
+use crate::utils::output;
+
+#[derive(Error, Debug)]
+pub enum DataLoaderError {
+    #[error("Failed to read data file\n  Path: {path}\n  Reason: {source}")]
+    ReadDataError {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Failed to parse data file\n  Path: {path}\n  Reason: {reason}")]
+    ParseDataError {
+        path: PathBuf,
+        reason: String,
+    },
+}
+
+/// Parses the data files under `data_dir` (exposed as `site.data`).
+///   - Supported: .yaml / .yml, .json, .toml, .csv (a list of rows keyed by the header)
+///   - Keys are file stems; nested directories become nested maps
+///     (e.g., data/talks/2025.yaml -> site.data.talks["2025"])
+pub fn load_data_dir(data_dir: &Path) -> Result<BTreeMap<String, Value>, DataLoaderError> {
+    if !data_dir.is_dir() {
+        return Ok(BTreeMap::new());
+    }
+
+    let mut data = BTreeMap::new();
+    load_dir_into(data_dir, &mut data)?;
+    Ok(data)
+}
+
+fn load_dir_into(dir: &Path, data: &mut BTreeMap<String, Value>) -> Result<(), DataLoaderError> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| DataLoaderError::ReadDataError { path: dir.to_path_buf(), source: e })?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .collect();
+    entries.sort();
+
+    for path in entries {
+        let Some(key) = path.file_stem().and_then(|s| s.to_str()).map(|s| s.to_string()) else {
+            continue;
+        };
+        if key.starts_with('.') {
+            continue;
+        }
+
+        let value = if path.is_dir() {
+            let mut nested = BTreeMap::new();
+            load_dir_into(&path, &mut nested)?;
+            if nested.is_empty() {
+                continue;
+            }
+            Value::Mapping(nested.into_iter().map(|(k, v)| (Value::String(k), v)).collect::<Mapping>())
+        } else {
+            match load_data_file(&path)? {
+                Some(value) => value,
+                None => continue,   // Not a data file (images, downloads, ...)
+            }
+        };
+
+        // e.g., authors.yaml and authors.json: the first one (by file name) wins
+        if data.contains_key(&key) {
+            output::warning(&format!("Duplicate data key '{}', ignoring {}", key, path.display()));
+            continue;
+        }
+        data.insert(key, value);
+    }
+
+    Ok(())
+}
+
+fn load_data_file(path: &Path) -> Result<Option<Value>, DataLoaderError> {
+    let ext = path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default();
+    if !matches!(ext.as_str(), "yaml" | "yml" | "json" | "toml" | "csv") {
+        return Ok(None);
+    }
+
+    let text = fs::read_to_string(path)
+        .map_err(|e| DataLoaderError::ReadDataError { path: path.to_path_buf(), source: e })?;
+
+    let parse_error = |reason: String| DataLoaderError::ParseDataError { path: path.to_path_buf(), reason };
+
+    let value = match ext.as_str() {
+        "yaml" | "yml" => {
+            if text.trim().is_empty() {
+                Value::Null
+            } else {
+                serde_yaml::from_str(&text).map_err(|e| parse_error(e.to_string()))?
+            }
+        }
+        "json" => serde_json::from_str(&text).map_err(|e| parse_error(e.to_string()))?,
+        "toml" => {
+            let table: toml::Table = toml::from_str(&text).map_err(|e| parse_error(e.to_string()))?;
+            toml_to_yaml(toml::Value::Table(table))
+        }
+        _ => parse_csv(&text).map_err(|e| parse_error(e.to_string()))?,
+    };
+
+    Ok(Some(value))
+}
+
+// TOML datetimes become strings (the same form as in the file)
+fn toml_to_yaml(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::Number(i.into()),
+        toml::Value::Float(f) => Value::Number(f.into()),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(dt) => Value::String(dt.to_string()),
+        toml::Value::Array(items) => Value::Sequence(items.into_iter().map(toml_to_yaml).collect()),
+        toml::Value::Table(table) => Value::Mapping(table.into_iter()
+            .map(|(k, v)| (Value::String(k), toml_to_yaml(v)))
+            .collect()),
+    }
+}
+
+// Rows as maps keyed by the header row; all values are strings
+fn parse_csv(text: &str) -> Result<Value, csv::Error> {
+    let mut reader = csv::Reader::from_reader(text.as_bytes());
+    let headers = reader.headers()?.clone();
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let row: Mapping = headers.iter()
+            .zip(record.iter())
+            .map(|(header, field)| (Value::String(header.to_string()), Value::String(field.to_string())))
+            .collect();
+        rows.push(Value::Mapping(row));
+    }
+
+    Ok(Value::Sequence(rows))
+}
@@ -0,0 +1,89 @@
+// src/content/git_history.rs
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum GitHistoryError {
+    #[error("Failed to run git\n  Path: {path}\n  Reason: {source}")]
+    RunGitError { path: PathBuf, source: std::io::Error },
+
+    #[error("git command failed\n  Path: {path}\n  Reason: {stderr}")]
+    GitCommandError { path: PathBuf, stderr: String },
+}
+
+/// Last commit touching a content file
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GitInfo {
+    pub hash: String,
+    pub short_hash: String,
+    pub author: String,
+    pub date: DateTime<FixedOffset>,
+}
+
+/// Reads the last commit of every file under `content_dir` with a single `git log` pass.
+///
+/// Keys are canonical file paths.
+pub fn load_last_commits(content_dir: &Path) -> Result<BTreeMap<PathBuf, GitInfo>, GitHistoryError> {
+    let repo_root = run_git(content_dir, &["rev-parse", "--show-toplevel"])?;
+    let repo_root = PathBuf::from(repo_root.trim());
+
+    // Newest first: the first commit listing a file is its last change
+    //   \x1e<hash>\x1f<author>\x1f<author date>
+    //   <file>
+    //   ...
+    let log = run_git(content_dir, &[
+        "-c", "core.quotepath=off",
+        "log", "--format=%x1e%H%x1f%an%x1f%aI", "--name-only", "--no-renames", "--", ".",
+    ])?;
+
+    let mut commits = BTreeMap::new();
+    for record in log.split('\x1e').filter(|r| !r.trim().is_empty()) {
+        let mut lines = record.lines();
+
+        let header: Vec<&str> = lines.next().unwrap_or_default().split('\x1f').collect();
+        let (hash, author, date) = match header.as_slice() {
+            [hash, author, date] => (*hash, *author, *date),
+            _ => continue,
+        };
+        let Ok(date) = DateTime::parse_from_rfc3339(date) else { continue };
+
+        let info = GitInfo {
+            hash: hash.to_string(),
+            short_hash: hash.chars().take(7).collect(),
+            author: author.to_string(),
+            date,
+        };
+
+        for file in lines.map(str::trim).filter(|l| !l.is_empty()) {
+            let path = repo_root.join(file);
+            let path = path.canonicalize().unwrap_or(path);
+            commits.entry(path).or_insert_with(|| info.clone());
+        }
+    }
+
+    Ok(commits)
+}
+
+fn run_git(dir: &Path, args: &[&str]) -> Result<String, GitHistoryError> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| GitHistoryError::RunGitError { path: dir.to_path_buf(), source: e })?;
+
+    if !output.status.success() {
+        return Err(GitHistoryError::GitCommandError {
+            path: dir.to_path_buf(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -2,6 +2,8 @@
 
 pub mod content_source;
 pub mod content_loader;
+pub mod data_loader;
+pub mod git_history;
 
 pub use content_source::{ContentKind,SourceId, SourceMeta, ContentFrontMatter, ContentSource};
 pub use content_source::MarkdownBody;
\ No newline at end of file
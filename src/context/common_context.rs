@@ -38,6 +38,22 @@ pub struct NavLink {
     pub url: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct TranslationLink {
+    pub language: String,   // e.g., "ko"
+    pub name: String,       // e.g., "한국어"
+    pub title: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LanguageLink {
+    pub code: String,
+    pub name: String,
+    pub url: String,        // Home page of the language
+    pub is_current: bool,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct Pagination {
     pub page: usize,
@@ -60,6 +76,8 @@ pub struct SiteContext {
     pub path: String,
     pub description: String,
     pub language: String,
+    pub home_url: String,               // e.g., "/" or "/ko/"
+    pub languages: Vec<LanguageLink>,   // All site languages (empty for single-language sites)
 
     pub author: Option<String>,
     pub email: Option<String>,
@@ -69,4 +87,6 @@ pub struct SiteContext {
     pub recent_posts: Vec<PostListItem>,
 
     pub theme: BTreeMap<String, serde_yaml::Value>,
+    pub data: BTreeMap<String, serde_yaml::Value>,     // content/data/ files (YAML, JSON, TOML, CSV)
+    pub i18n: BTreeMap<String, String>,     // UI strings for the current language
 }
\ No newline at end of file
@@ -7,8 +7,9 @@ use serde_yaml::Value;
 use chrono::{DateTime, FixedOffset};
 
 use crate::content::content_source::{ContentKind};
+use crate::content::git_history::GitInfo;
 
-use super::{TaxonomyItem, NavLink};
+use super::{TaxonomyItem, NavLink, TranslationLink};
 
 
 #[derive(Debug, Clone, Serialize)]
@@ -17,10 +18,12 @@ pub struct ContentContext {
     pub title: String,
     pub url: String,
     pub description: Option<String>,
-    pub language: Option<String>,
+    pub language: String,
+    pub translation_key: String,
 
     pub date: DateTime<FixedOffset>,
     pub updated: Option<DateTime<FixedOffset>>,
+    pub git: Option<GitInfo>,   // Last commit of the source file (build.git_dates)
 
     pub content_html: String,
 
@@ -33,4 +36,6 @@ pub struct ContentContext {
 
     pub prev: Option<NavLink>,
     pub next: Option<NavLink>,
+
+    pub translations: Vec<TranslationLink>,     // Other languages of this content (language switchers)
 }
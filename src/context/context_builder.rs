@@ -3,32 +3,54 @@
 use std::collections::{BTreeMap, BTreeSet};
 
 use chrono::{Datelike};
-use comrak::{markdown_to_html_with_plugins, Options, options::Plugins};
-use comrak::plugins::syntect::SyntectAdapter;
 
 use crate::config::blogconfig::{BlogConfig};
+use crate::config::i18n::UiStrings;
 use crate::config::theme::{TaxonomyConfig, ArchiveConfig, ArchiveKind};
 use crate::content::content_source::{ContentKind, ContentSource};
 use crate::utils::filters::slugify;
+use crate::utils::markdown::MarkdownRenderer;
 
 use super::{SiteContext, TaxonomyItem, ArchiveItem, NavLink, Pagination, PageLink};
+use super::{TranslationLink, LanguageLink};
 use super::{ListContext, ListKind, PostListItem};
 use super::{ContentContext};
 
 
 pub fn build_site_context(
     blog_config: &BlogConfig, 
+    language: &str,
     taxonomies: &BTreeMap<String, Vec<TaxonomyItem>>,
     archives: &Vec<ArchiveItem>,
     recent_posts: &Vec<PostListItem>,
-    theme_others: &BTreeMap<String, serde_yaml::Value>
+    theme_others: &BTreeMap<String, serde_yaml::Value>,
+    strings: &UiStrings,
 ) -> SiteContext {
+    let language_config = blog_config.languages.get(language);
+
+    let languages = if blog_config.is_multilingual() {
+        blog_config.language_codes().into_iter()
+            .map(|code| LanguageLink {
+                name: blog_config.language_name(&code),
+                url: format!("{}/", blog_config.language_prefix(&code)),
+                is_current: code == language,
+                code,
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
     SiteContext {
-        title: blog_config.site.name.clone(),
+        title: language_config.and_then(|l| l.title.clone())
+            .unwrap_or_else(|| blog_config.site.name.clone()),
         base_url: blog_config.site.base_url.clone(),
         path: blog_config.site.path.clone(),
-        description: blog_config.site.description.clone(),
-        language: blog_config.site.language.clone(),
+        description: language_config.and_then(|l| l.description.clone())
+            .unwrap_or_else(|| blog_config.site.description.clone()),
+        language: language.to_string(),
+        home_url: format!("{}/", blog_config.language_prefix(language)),
+        languages,
 
         author: Some(blog_config.author.name.clone()),
         email: Some(blog_config.author.email.clone()),
@@ -38,6 +60,8 @@ pub fn build_site_context(
         recent_posts: recent_posts.clone(),
 
         theme: theme_others.clone(),
+        data: BTreeMap::new(),      // Filled by the build (content/data/ is shared across languages)
+        i18n: strings.as_map().clone(),
     }
 }
 
@@ -46,28 +70,10 @@ pub fn build_content_contexts(
     contents: &[&ContentSource],
     site_taxonomies: &BTreeMap<String, Vec<TaxonomyItem>>,
 ) -> Vec<ContentContext> {
-    let mut options = Options::default();
-
-    // Extension options
-    options.extension.strikethrough = true;
-    options.extension.table = true;
-    options.extension.autolink = true;
-    options.extension.tasklist = true;
-    options.extension.header_ids = Some(String::new());
-    options.extension.footnotes = true;
-
-    // Render options
-    options.render.hardbreaks = false;
-    options.render.github_pre_lang = true;
-    options.render.r#unsafe = true;
-
-    let adapter = SyntectAdapter::new(None);
-
-    let mut plugins = Plugins::default();
-    plugins.render.codefence_syntax_highlighter = Some(&adapter);
+    let markdown = MarkdownRenderer::new();
 
     contents.iter().map(|content| {
-        let dt = &content.front_matter.date;
+        let dt = &content.date;
         let y = format!("{:04}", dt.year());
         let m = format!("{:02}", dt.month());
         let d = format!("{:02}", dt.day());
@@ -80,7 +86,9 @@ pub fn build_content_contexts(
             ContentKind::Page => &blog_config.permalinks.page,
         };
 
-        let url_path = build_url_path(pattern, &y, &m, &d, &slug);
+        let url_path = format!("{}{}",
+            blog_config.language_prefix(&content.language),
+            build_url_path(pattern, &y, &m, &d, &slug));
 
         let taxonomies = extract_post_taxonomies(
             &content.front_matter.taxonomies,
@@ -90,7 +98,7 @@ pub fn build_content_contexts(
         let absolute_thumbnail = content.front_matter.thumbnail.as_ref()
             .map(|t| resolve_thumbnail_path(t, &url_path));
 
-        let content_html = markdown_to_html_with_plugins(&content.body.markdown, &options, &plugins);
+        let content_html = markdown.render(&content.body.markdown);
 
         ContentContext{
             kind: content.kind,
@@ -98,10 +106,12 @@ pub fn build_content_contexts(
             title: content.front_matter.title.clone(),
             url: url_path,
             description: content.front_matter.description.clone(),
-            language: content.front_matter.language.clone(),
+            language: content.language.clone(),
+            translation_key: content.translation_key.clone(),
 
-            date: content.front_matter.date.clone(),
-            updated: content.front_matter.updated.clone(),
+            date: content.date,
+            updated: content.updated,
+            git: content.git.clone(),
 
             content_html: content_html,
 
@@ -112,10 +122,40 @@ pub fn build_content_contexts(
             thumbnail: absolute_thumbnail,
             prev: None,
             next: None,
+            translations: Vec::new(),
         }
     }).collect()
 }
 
+// Link contents sharing a translation key across all languages
+pub fn link_translations(blog_config: &BlogConfig, context_groups: &mut [&mut Vec<ContentContext>]) {
+    let mut translations: BTreeMap<(ContentKind, String), Vec<TranslationLink>> = BTreeMap::new();
+
+    for contexts in context_groups.iter() {
+        for ctx in contexts.iter() {
+            translations.entry((ctx.kind, ctx.translation_key.clone()))
+                .or_default()
+                .push(TranslationLink {
+                    language: ctx.language.clone(),
+                    name: blog_config.language_name(&ctx.language),
+                    title: ctx.title.clone(),
+                    url: ctx.url.clone(),
+                });
+        }
+    }
+
+    for contexts in context_groups.iter_mut() {
+        for ctx in contexts.iter_mut() {
+            if let Some(links) = translations.get(&(ctx.kind, ctx.translation_key.clone())) {
+                ctx.translations = links.iter()
+                    .filter(|link| link.language != ctx.language)
+                    .cloned()
+                    .collect();
+            }
+        }
+    }
+}
+
 pub fn build_list_context(
     title: String,
     url: String,
@@ -198,6 +238,7 @@ fn resolve_thumbnail_path(thumbnail: &str, post_url: &str) -> String {
 pub fn build_taxonomies_index(
     posts: &[&ContentSource],
     taxonomy_configs: &[TaxonomyConfig],
+    url_prefix: &str,
 ) -> BTreeMap<String, Vec<TaxonomyItem>> {
     let mut taxonomies = BTreeMap::new();
 
@@ -206,7 +247,8 @@ pub fn build_taxonomies_index(
             continue;
         }
 
-        let items = build_single_taxonomy_index(posts, &config.name, &config.permalink);
+        let permalink = format!("{}{}", url_prefix, config.permalink);
+        let items = build_single_taxonomy_index(posts, &config.name, &permalink);
         taxonomies.insert(config.name.clone(), items);
     }
 
@@ -289,12 +331,14 @@ fn extract_post_taxonomies(
 // Build archives index
 pub fn build_archives_index(
     posts: &[&ContentSource], 
-    archive_configs: &[ArchiveConfig]
+    archive_configs: &[ArchiveConfig],
+    url_prefix: &str,
 ) -> Vec<ArchiveItem> {
     let mut archives = Vec::new();
 
     for config in archive_configs {
-        let items = build_single_archive_index(posts, &config.kind, &config.permalink);
+        let permalink = format!("{}{}", url_prefix, config.permalink);
+        let items = build_single_archive_index(posts, &config.kind, &permalink);
         archives.extend(items);
     }
 
@@ -309,7 +353,7 @@ fn build_single_archive_index(
     let mut counts: BTreeMap<(u32, Option<u32>, Option<u32>), usize> = BTreeMap::new();
 
     for post in posts.iter() {
-        let dt = &post.front_matter.date;
+        let dt = &post.date;
         let year: u32 = dt.year() as u32;
         let month: u32 = dt.month();
         let day: u32 = dt.day();
@@ -438,11 +482,13 @@ pub fn group_posts_by_archive(
 // * `current_page` - Current page number (1-indexed)
 // * `per_page` - Number of items per page (e.g., 10)
 // * `total_items` - Total number of items
+// * `strings` - UI strings for link titles ("page", "first", "last")
 pub fn build_pagination(
     base_url: &str,
     current_page: usize,
     per_page: usize,
     total_items: usize,
+    strings: &UiStrings,
 ) -> Pagination {
     let total_pages = if per_page == 0 || total_items == 0 {
         1
@@ -457,7 +503,7 @@ pub fn build_pagination(
         let prev_page = current_page - 1;
         let url = page_url(base_url, prev_page);
         Some(NavLink {
-            title: format!("Page {}", prev_page),
+            title: strings.format("page", &[("page", &prev_page.to_string())]),
             url,
         })
     } else {
@@ -468,7 +514,7 @@ pub fn build_pagination(
         let next_page = current_page + 1;
         let url = page_url(base_url, next_page);
         Some(NavLink {
-            title: format!("Page {}", next_page),
+            title: strings.format("page", &[("page", &next_page.to_string())]),
             url,
         })
     } else {
@@ -477,7 +523,7 @@ pub fn build_pagination(
 
     let first = if current_page > 1 {
         Some(NavLink {
-            title: "First".to_string(),
+            title: strings.get("first").to_string(),
             url: base_url.to_string(),
         })
     } else {
@@ -486,7 +532,7 @@ pub fn build_pagination(
 
     let last = if current_page < total_pages && total_pages > 1 {
         Some(NavLink {
-            title: "Last".to_string(),
+            title: strings.get("last").to_string(),
             url: page_url(base_url, total_pages),
         })
     } else {
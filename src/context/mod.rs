@@ -6,7 +6,9 @@ pub mod list_context;
 pub mod content_context;
 
 pub mod context_builder;
+pub mod schema;
 
 pub use common_context::{SiteContext, TaxonomyItem, ArchiveItem, NavLink, Pagination, PageLink};
+pub use common_context::{TranslationLink, LanguageLink};
 pub use list_context::{ListContext, ListKind, PostListItem};
 pub use content_context::{ContentContext};
\ No newline at end of file
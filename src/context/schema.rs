@@ -0,0 +1,144 @@
+// src/context/schema.rs
+
+/// Template variables and their fields, as documented in docs/template-context.md
+///
+/// Used by `theme check` to report unknown variables. Only the variable and its first field
+/// are checked: free-form maps (`site.theme`, `site.data`, `post.extra`, ...) accept any key.
+pub const TEMPLATE_VARIABLES: &[(&str, &[&str])] = &[
+    ("site", SITE_FIELDS),
+    ("post", CONTENT_FIELDS),
+    ("page", CONTENT_FIELDS),
+    ("list", LIST_FIELDS),
+    ("home", LIST_FIELDS),
+];
+
+const SITE_FIELDS: &[&str] = &[
+    "title", "base_url", "path", "description", "language", "home_url", "languages",
+    "author", "email", "taxonomies", "archives", "recent_posts", "theme", "data", "i18n",
+];
+
+const CONTENT_FIELDS: &[&str] = &[
+    "kind", "title", "url", "description", "language", "translation_key",
+    "date", "updated", "git", "content_html", "extra", "taxonomies",
+    "summary", "thumbnail", "prev", "next", "translations",
+];
+
+const LIST_FIELDS: &[&str] = &[
+    "title", "url", "description", "list_kind", "posts", "pagination",
+];
+
+/// Checks a dotted variable path (e.g., "post.titel") against the schema
+///
+/// Returns `None` if the variable is known, or the unknown part otherwise.
+pub fn unknown_variable(path: &str) -> Option<String> {
+    let mut parts = path.split('.');
+    let name = parts.next().unwrap_or_default();
+
+    let Some((_, fields)) = TEMPLATE_VARIABLES.iter().find(|(variable, _)| *variable == name) else {
+        return Some(name.to_string());
+    };
+
+    match parts.next() {
+        Some(field) if !fields.contains(&field) => Some(format!("{}.{}", name, field)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::DateTime;
+    use serde::Serialize;
+
+    use super::*;
+    use crate::content::content_source::ContentKind;
+    use crate::context::{ContentContext, ListContext, ListKind, Pagination, SiteContext};
+
+    fn keys(value: &impl Serialize) -> Vec<String> {
+        let serde_json::Value::Object(map) = serde_json::to_value(value).unwrap() else {
+            panic!("not an object");
+        };
+        let mut keys: Vec<String> = map.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    fn sorted(fields: &[&str]) -> Vec<String> {
+        let mut fields: Vec<String> = fields.iter().map(|f| f.to_string()).collect();
+        fields.sort();
+        fields
+    }
+
+    #[test]
+    fn fields_match_the_contexts() {
+        let site = SiteContext {
+            title: String::new(),
+            base_url: String::new(),
+            path: String::new(),
+            description: String::new(),
+            language: String::new(),
+            home_url: String::new(),
+            languages: Vec::new(),
+            author: None,
+            email: None,
+            taxonomies: BTreeMap::new(),
+            archives: Vec::new(),
+            recent_posts: Vec::new(),
+            theme: BTreeMap::new(),
+            data: BTreeMap::new(),
+            i18n: BTreeMap::new(),
+        };
+        let content = ContentContext {
+            kind: ContentKind::Post,
+            title: String::new(),
+            url: String::new(),
+            description: None,
+            language: String::new(),
+            translation_key: String::new(),
+            date: DateTime::parse_from_rfc3339("2024-01-15T00:00:00Z").unwrap(),
+            updated: None,
+            git: None,
+            content_html: String::new(),
+            extra: BTreeMap::new(),
+            taxonomies: None,
+            summary: None,
+            thumbnail: None,
+            prev: None,
+            next: None,
+            translations: Vec::new(),
+        };
+        let list = ListContext {
+            title: String::new(),
+            url: String::new(),
+            description: None,
+            list_kind: ListKind::Home,
+            posts: Vec::new(),
+            pagination: Pagination {
+                page: 1,
+                per_page: 10,
+                total_items: 0,
+                total_pages: 1,
+                has_prev: false,
+                has_next: false,
+                prev: None,
+                next: None,
+                first: None,
+                last: None,
+                pages: Vec::new(),
+            },
+        };
+
+        assert_eq!(keys(&site), sorted(SITE_FIELDS));
+        assert_eq!(keys(&content), sorted(CONTENT_FIELDS));
+        assert_eq!(keys(&list), sorted(LIST_FIELDS));
+    }
+
+    #[test]
+    fn unknown_variables() {
+        assert_eq!(unknown_variable("post.title"), None);
+        assert_eq!(unknown_variable("site.theme.accent"), None);
+        assert_eq!(unknown_variable("post.titel"), Some("post.titel".to_string()));
+        assert_eq!(unknown_variable("posts"), Some("posts".to_string()));
+    }
+}
@@ -0,0 +1,292 @@
+// src/utils/assets.rs
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::config::theme::ThemePackage;
+use crate::utils::{minify, output, url_helpers};
+
+#[derive(Error, Debug)]
+pub enum AssetError {
+    #[error("Failed to read theme asset\n  Path: {path}\n  Reason: {source}")]
+    ReadAssetError {
+        path: PathBuf,
+        source: io::Error,
+    },
+
+    #[error("Failed to compile Sass\n  Path: {location}\n  Reason: {reason}")]
+    SassCompileError {
+        location: String,   // "<file>:<line>:<column>" of the error (may be a partial)
+        reason: String,
+    },
+}
+
+/// A file of the theme's assets/ as it will be written to public/assets/
+#[derive(Debug)]
+pub struct ThemeAsset {
+    pub path: String,       // Relative to assets/ (e.g., "css/style.css")
+    pub data: AssetData,
+    pub fingerprint: Option<String>,    // Content hash in the file name (`build.fingerprint`)
+}
+
+#[derive(Debug)]
+pub enum AssetData {
+    File(PathBuf),          // Copied as-is
+    Generated(Vec<u8>),     // e.g., CSS compiled from Sass
+}
+
+impl ThemeAsset {
+    pub fn bytes(&self) -> io::Result<Cow<'_, [u8]>> {
+        match &self.data {
+            AssetData::File(path) => fs::read(path).map(Cow::Owned),
+            AssetData::Generated(bytes) => Ok(Cow::Borrowed(bytes)),
+        }
+    }
+
+    /// Path written under assets/ ("css/style.css" -> "css/style.1a2b3c4d.css" when fingerprinted)
+    pub fn output_path(&self) -> String {
+        let Some(hash) = &self.fingerprint else {
+            return self.path.clone();
+        };
+
+        let (dir, file_name) = match self.path.rsplit_once('/') {
+            Some((dir, file_name)) => (format!("{}/", dir), file_name),
+            None => (String::new(), self.path.as_str()),
+        };
+        match file_name.rsplit_once('.') {
+            Some((stem, ext)) if !stem.is_empty() => format!("{}{}.{}.{}", dir, stem, hash, ext),
+            _ => format!("{}{}.{}", dir, file_name, hash),
+        }
+    }
+}
+
+/// Collects the assets of a theme and its parents (files of the child theme win).
+///   - `.scss` / `.sass` files are compiled to `.css`; partials (`_name.scss`) are only imported
+///   - Theme settings (`site.theme`) are available as Sass variables through `@use "site-theme"`
+pub fn collect_theme_assets(theme_package: &ThemePackage) -> Result<Vec<ThemeAsset>, AssetError> {
+    let mut sources: BTreeMap<String, PathBuf> = BTreeMap::new();
+    for assets_dir in theme_package.assets_dirs.iter().rev() {
+        walk_files(assets_dir, assets_dir, &mut sources);
+    }
+
+    let sass_fs = SassFs::new(&theme_package.manifest.others);
+
+    // e.g., style.scss and style.css: the compiled file wins over the copied one
+    let mut assets: BTreeMap<String, AssetData> = BTreeMap::new();
+    for (rel_path, path) in sources {
+        let Some(stem) = rel_path.strip_suffix(".scss").or_else(|| rel_path.strip_suffix(".sass")) else {
+            assets.entry(rel_path).or_insert(AssetData::File(path));
+            continue;
+        };
+
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        if file_name.starts_with('_') {
+            continue;
+        }
+
+        let css = compile_sass(&path, &theme_package.assets_dirs, &sass_fs)?;
+        assets.insert(format!("{}.css", stem), AssetData::Generated(css.into_bytes()));
+    }
+
+    Ok(assets.into_iter().map(|(path, data)| ThemeAsset { path, data, fingerprint: None }).collect())
+}
+
+/// Minifies the CSS and JS assets in place (assets that fail to parse are kept as-is with a warning).
+/// Returns the number of minified files.
+pub fn minify_assets(assets: &mut [ThemeAsset]) -> Result<usize, AssetError> {
+    let mut minified = 0;
+    for asset in assets.iter_mut() {
+        let Some(ext) = asset.path.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase()) else {
+            continue;
+        };
+        if !matches!(ext.as_str(), "css" | "js" | "mjs") || asset.path.contains(".min.") {
+            continue;
+        }
+
+        let bytes = read_asset(asset)?;
+
+        let result = if ext == "css" {
+            minify::css(&String::from_utf8_lossy(&bytes)).map(String::into_bytes)
+        } else {
+            minify::js(&bytes, ext == "mjs")
+        };
+
+        match result {
+            Ok(data) => {
+                asset.data = AssetData::Generated(data);
+                minified += 1;
+            }
+            Err(reason) => output::warning(&format!("Skipping minification of assets/{}: {}", asset.path, reason)),
+        }
+    }
+    Ok(minified)
+}
+
+/// Adds a content hash to the file names of the CSS and JS assets (e.g., "css/style.1a2b3c4d.css").
+/// Other files (images, fonts) keep their names so relative `url()`s in stylesheets still resolve;
+/// the build also writes CSS and JS under their original name for imports between assets.
+/// Returns the manifest: original path -> fingerprinted path.
+pub fn fingerprint_assets(assets: &mut [ThemeAsset]) -> Result<BTreeMap<String, String>, AssetError> {
+    let mut manifest = BTreeMap::new();
+    for asset in assets.iter_mut() {
+        let ext = asset.path.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase());
+        if !matches!(ext.as_deref(), Some("css" | "js" | "mjs")) {
+            continue;
+        }
+
+        let bytes = read_asset(asset)?;
+        asset.fingerprint = Some(url_helpers::short_hash(&bytes));
+        manifest.insert(asset.path.clone(), asset.output_path());
+    }
+    Ok(manifest)
+}
+
+fn read_asset(asset: &ThemeAsset) -> Result<Cow<'_, [u8]>, AssetError> {
+    asset.bytes().map_err(|e| AssetError::ReadAssetError {
+        path: match &asset.data {
+            AssetData::File(path) => path.clone(),
+            AssetData::Generated(_) => PathBuf::from(&asset.path),
+        },
+        source: e,
+    })
+}
+
+fn walk_files(dir: &Path, root: &Path, files: &mut BTreeMap<String, PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            walk_files(&path, root, files);
+        } else if path.is_file() {
+            let rel = path.strip_prefix(root).unwrap_or(&path).to_string_lossy().replace('\\', "/");
+            files.insert(rel, path);
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// Sass
+//------------------------------------------------------------------------------
+
+// Virtual load path serving the "site-theme" module (never touches the disk)
+const SASS_VIRTUAL_DIR: &str = "/__xnbloggen__";
+const SASS_THEME_MODULE: &str = "_site-theme.scss";
+
+// File system for grass: the real file system plus the generated "site-theme" module
+#[derive(Debug)]
+struct SassFs {
+    theme_module: String,
+}
+
+impl SassFs {
+    fn new(theme_settings: &BTreeMap<String, serde_yaml::Value>) -> Self {
+        let mut variables = Vec::new();
+        for (key, value) in theme_settings {
+            flatten_variables(key, value, &mut variables);
+        }
+
+        let theme_module = variables.iter()
+            .map(|(name, value)| format!("${}: {};\n", name, value))
+            .collect();
+
+        Self { theme_module }
+    }
+
+    fn is_theme_module(path: &Path) -> bool {
+        path == Path::new(SASS_VIRTUAL_DIR).join(SASS_THEME_MODULE)
+    }
+}
+
+impl grass::Fs for SassFs {
+    fn is_dir(&self, path: &Path) -> bool {
+        path == Path::new(SASS_VIRTUAL_DIR) || path.is_dir()
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        Self::is_theme_module(path) || path.is_file()
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        if Self::is_theme_module(path) {
+            return Ok(self.theme_module.clone().into_bytes());
+        }
+        fs::read(path)
+    }
+}
+
+// colors: { primary: "#58a6ff" } -> $colors-primary: #58a6ff
+fn flatten_variables(name: &str, value: &serde_yaml::Value, variables: &mut Vec<(String, String)>) {
+    use serde_yaml::Value;
+
+    match value {
+        Value::Mapping(map) => {
+            for (key, value) in map {
+                if let Some(key) = key.as_str() {
+                    flatten_variables(&format!("{}-{}", name, key), value, variables);
+                }
+            }
+        }
+        Value::Sequence(items) => {
+            let items: Option<Vec<String>> = items.iter().map(sass_value).collect();
+            if let Some(items) = items {
+                variables.push((name.to_string(), format!("({})", items.join(", "))));
+            }
+        }
+        _ => {
+            if let Some(value) = sass_value(value) {
+                variables.push((name.to_string(), value));
+            }
+        }
+    }
+}
+
+// Scalars as Sass values: colors, numbers and lengths stay unquoted, unsafe strings are quoted
+fn sass_value(value: &serde_yaml::Value) -> Option<String> {
+    use serde_yaml::Value;
+
+    match value {
+        Value::Null => Some("null".to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::String(s) => {
+            let unsafe_chars = s.trim().is_empty()
+                || s.contains([';', '{', '}', '\n', '\\'])
+                || s.contains("//") || s.contains("/*") || s.contains("#{");
+            if unsafe_chars {
+                Some(format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\a ")))
+            } else {
+                Some(s.clone())
+            }
+        }
+        _ => None,
+    }
+}
+
+fn compile_sass(path: &Path, load_paths: &[PathBuf], sass_fs: &SassFs) -> Result<String, AssetError> {
+    let mut paths: Vec<PathBuf> = load_paths.to_vec();
+    paths.push(PathBuf::from(SASS_VIRTUAL_DIR));
+
+    let options = grass::Options::default()
+        .fs(sass_fs)
+        .load_paths(&paths);
+
+    grass::from_path(path, &options).map_err(|e| match e.kind() {
+        grass::ErrorKind::ParseError { message, loc, .. } => AssetError::SassCompileError {
+            location: format!("{}:{}:{}", loc.file.name(), loc.begin.line + 1, loc.begin.column + 1),
+            reason: message,
+        },
+        grass::ErrorKind::IoError(source) => AssetError::ReadAssetError {
+            path: path.to_path_buf(),
+            source: io::Error::new(source.kind(), source.to_string()),
+        },
+        other => AssetError::SassCompileError {
+            location: path.display().to_string(),
+            reason: format!("{:?}", other),
+        },
+    })
+}
@@ -0,0 +1,411 @@
+// src/utils/csp.rs
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use sha2::{Digest, Sha256};
+
+use crate::utils::html::{HtmlTag, parse_tag};
+use crate::utils::output_sink::OutputSink;
+
+// Directives written to the policy: (name, written even without sources other than 'self')
+const FETCH_DIRECTIVES: [(&str, bool); 8] = [
+    ("script-src", true),
+    ("style-src", true),
+    ("img-src", true),
+    ("font-src", true),
+    ("media-src", false),
+    ("frame-src", false),
+    ("manifest-src", false),
+    ("form-action", true),
+];
+
+const FONT_EXTENSIONS: [&str; 5] = ["woff", "woff2", "ttf", "otf", "eot"];
+
+/// CSP hash source of an inline script or style ("'sha256-<base64>'")
+pub fn hash_source(content: &[u8]) -> String {
+    format!("'sha256-{}'", BASE64.encode(Sha256::digest(content)))
+}
+
+//------------------------------------------------------------------------------
+// CspCollector - sources used by the generated pages and stylesheets
+//------------------------------------------------------------------------------
+#[derive(Debug, Default)]
+pub struct CspCollector {
+    site_origin: Option<String>,                        // Own absolute URLs are covered by 'self'
+    sources: BTreeMap<&'static str, BTreeSet<String>>,  // directive -> sources besides 'self'
+    inline_styles: BTreeSet<String>,                   // <style> hashes
+    style_attributes: bool,                             // style="..." needs 'unsafe-inline'
+    inline_handlers: BTreeSet<String>,                  // Pages with on*= handlers or javascript: URLs
+}
+
+impl CspCollector {
+    pub fn new(base_url: &str) -> Self {
+        CspCollector { site_origin: url_origin(base_url), ..CspCollector::default() }
+    }
+
+    /// Content-Security-Policy value covering everything collected so far
+    pub fn policy(&self) -> String {
+        let mut directives = vec!["default-src 'self'".to_string()];
+
+        for (directive, always) in FETCH_DIRECTIVES {
+            let extra = self.sources.get(directive);
+            let is_style = directive == "style-src";
+            if !always && extra.is_none() {
+                continue;
+            }
+
+            let mut sources = vec!["'self'".to_string()];
+            sources.extend(extra.into_iter().flatten().cloned());
+            if is_style {
+                // Hashes would disable 'unsafe-inline', which style attributes (e.g., syntax highlighting) need
+                if self.style_attributes {
+                    sources.push("'unsafe-inline'".to_string());
+                } else {
+                    sources.extend(self.inline_styles.iter().cloned());
+                }
+            }
+            directives.push(format!("{} {}", directive, sources.join(" ")));
+        }
+
+        directives.push("object-src 'none'".to_string());
+        directives.push("base-uri 'self'".to_string());
+        directives.join("; ")
+    }
+
+    /// Pages using inline event handlers or `javascript:` URLs (blocked by the policy)
+    pub fn inline_handler_pages(&self) -> Vec<&str> {
+        self.inline_handlers.iter().map(String::as_str).collect()
+    }
+
+    pub fn scan_html(&mut self, rel_path: &str, html: &str) {
+        // ASCII lowercasing keeps byte offsets, so both strings can be indexed alike
+        let lower = html.to_ascii_lowercase();
+        let mut pos = 0;
+
+        while let Some(offset) = html[pos..].find('<') {
+            let start = pos + offset + 1;
+            if html[start..].starts_with("!--") {
+                pos = lower[start..].find("-->").map_or(html.len(), |end| start + end + 3);
+                continue;
+            }
+
+            let Some(HtmlTag { name: tag, attributes, end }) = parse_tag(html, start) else {
+                pos = start;
+                continue;
+            };
+            pos = end;
+
+            // Raw text elements: the content runs up to the closing tag
+            let mut content = "";
+            if tag == "script" || tag == "style" {
+                let content_end = lower[pos..].find(&format!("</{}", tag)).map_or(html.len(), |i| pos + i);
+                content = &html[pos..content_end];
+                pos = content_end;
+            }
+
+            self.scan_tag(rel_path, &tag, &attributes, content);
+        }
+    }
+
+    pub fn scan_css(&mut self, css: &str) {
+        let lower = css.to_ascii_lowercase();
+
+        for (start, _) in lower.match_indices("url(") {
+            let value_start = start + 4;
+            let value_end = css[value_start..].find(')').map_or(css.len(), |i| value_start + i);
+            let url = css[value_start..value_end].trim().trim_matches(['"', '\'']);
+
+            let directive = if lower[..start].trim_end().ends_with("@import") {
+                "style-src"
+            } else if is_font_url(url) {
+                "font-src"
+            } else {
+                "img-src"
+            };
+            self.add_source(directive, url);
+        }
+
+        // @import "https://..." (without url())
+        for (start, _) in lower.match_indices("@import") {
+            let rest = css[start + 7..].trim_start();
+            if let Some(quote) = rest.chars().next().filter(|c| *c == '"' || *c == '\'') {
+                let url = rest[1..].split(quote).next().unwrap_or_default();
+                self.add_source("style-src", url);
+            }
+        }
+    }
+
+    fn scan_tag(&mut self, rel_path: &str, tag: &str, attributes: &[(String, String)], content: &str) {
+        let attr = |name: &str| attributes.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str());
+
+        for (name, value) in attributes {
+            let is_handler = name.len() > 2 && name.starts_with("on");
+            if is_handler || value.trim_start().to_ascii_lowercase().starts_with("javascript:") {
+                self.inline_handlers.insert(rel_path.to_string());
+            }
+            if name == "style" {
+                self.style_attributes = true;
+            }
+        }
+
+        match tag {
+            "script" => match attr("src") {
+                Some(src) => self.add_source("script-src", src),
+                None if is_script_type(attr("type")) && !content.is_empty() => {
+                    self.sources.entry("script-src").or_default().insert(hash_source(content.as_bytes()));
+                }
+                None => {}
+            },
+            "style" => {
+                self.inline_styles.insert(hash_source(content.as_bytes()));
+                self.scan_css(content);
+            }
+            "link" => {
+                let Some(href) = attr("href") else { return };
+                let rel = attr("rel").unwrap_or_default().to_ascii_lowercase();
+                for rel in rel.split_whitespace() {
+                    let directive = match (rel, attr("as")) {
+                        ("stylesheet", _) | ("preload", Some("style")) => "style-src",
+                        ("modulepreload", _) | ("preload", Some("script")) => "script-src",
+                        ("preload", Some("font")) => "font-src",
+                        ("icon" | "apple-touch-icon" | "mask-icon", _) | ("preload", Some("image")) => "img-src",
+                        ("manifest", _) => "manifest-src",
+                        _ => continue,
+                    };
+                    self.add_source(directive, href);
+                }
+            }
+            "img" => {
+                self.add_optional_source("img-src", attr("src"));
+                self.add_srcset("img-src", attr("srcset"));
+            }
+            "source" => {
+                self.add_optional_source("media-src", attr("src"));
+                self.add_srcset("img-src", attr("srcset"));
+            }
+            "video" => {
+                self.add_optional_source("media-src", attr("src"));
+                self.add_optional_source("img-src", attr("poster"));
+            }
+            "audio" | "track" => self.add_optional_source("media-src", attr("src")),
+            "iframe" => self.add_optional_source("frame-src", attr("src")),
+            "form" => self.add_optional_source("form-action", attr("action")),
+            _ => {}
+        }
+    }
+
+    fn add_optional_source(&mut self, directive: &'static str, url: Option<&str>) {
+        if let Some(url) = url {
+            self.add_source(directive, url);
+        }
+    }
+
+    // srcset="a.jpg 480w, https://cdn.example.com/b.jpg 800w"
+    fn add_srcset(&mut self, directive: &'static str, srcset: Option<&str>) {
+        for candidate in srcset.unwrap_or_default().split(',') {
+            if let Some(url) = candidate.split_whitespace().next() {
+                self.add_source(directive, url);
+            }
+        }
+    }
+
+    // "//blog.example.com" (no scheme) is the site too
+    fn is_site_origin(&self, origin: &str) -> bool {
+        self.site_origin.as_deref().is_some_and(|site| {
+            site == origin || (!origin.contains("://") && site.split_once("://").is_some_and(|(_, host)| host == origin))
+        })
+    }
+
+    // Relative URLs are covered by 'self'; other origins and data: URLs are added
+    fn add_source(&mut self, directive: &'static str, url: &str) {
+        let url = url.trim();
+        let source = if url.to_ascii_lowercase().starts_with("data:") {
+            "data:".to_string()
+        } else {
+            match url_origin(url) {
+                Some(origin) if !self.is_site_origin(&origin) => origin,
+                _ => return,
+            }
+        };
+        self.sources.entry(directive).or_default().insert(source);
+    }
+}
+
+// "https://cdn.example.com:8443/lib.js" -> "https://cdn.example.com:8443", "//cdn.example.com/x" -> "cdn.example.com"
+fn url_origin(url: &str) -> Option<String> {
+    let host_of = |rest: &str| rest.split(['/', '?', '#']).next().filter(|host| !host.is_empty()).map(str::to_ascii_lowercase);
+
+    if let Some(rest) = url.strip_prefix("//") {
+        return host_of(rest);
+    }
+
+    let (scheme, rest) = url.split_once("://")?;
+    let scheme = scheme.to_ascii_lowercase();
+    if scheme != "http" && scheme != "https" {
+        return None;
+    }
+    host_of(rest).map(|host| format!("{}://{}", scheme, host))
+}
+
+fn is_font_url(url: &str) -> bool {
+    let url = url.to_ascii_lowercase();
+    if url.starts_with("data:") {
+        return url.starts_with("data:font/") || url.starts_with("data:application/font")
+            || url.starts_with("data:application/x-font");
+    }
+
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    path.rsplit_once('.').is_some_and(|(_, ext)| FONT_EXTENSIONS.contains(&ext))
+}
+
+// Data blocks such as JSON-LD are not executed and don't need a hash
+fn is_script_type(script_type: Option<&str>) -> bool {
+    let script_type = script_type.unwrap_or_default().trim().to_ascii_lowercase();
+    script_type.is_empty()
+        || script_type == "module"
+        || script_type == "importmap"
+        || script_type.contains("javascript")
+        || script_type.contains("ecmascript")
+}
+
+//------------------------------------------------------------------------------
+// CspSink - collects the sources of the generated HTML and CSS before handing them to another sink
+//------------------------------------------------------------------------------
+pub struct CspSink<'a> {
+    inner: &'a mut dyn OutputSink,
+    collector: CspCollector,
+}
+
+impl<'a> CspSink<'a> {
+    pub fn new(inner: &'a mut dyn OutputSink, base_url: &str) -> Self {
+        CspSink { inner, collector: CspCollector::new(base_url) }
+    }
+
+    pub fn collector(&self) -> &CspCollector {
+        &self.collector
+    }
+
+    /// `_headers` file (Netlify / Cloudflare Pages format) applying the policy to every path
+    pub fn headers_file(&self) -> String {
+        format!("/*\n  Content-Security-Policy: {}\n", self.collector.policy())
+    }
+
+    fn scan(&mut self, rel_path: &str, data: &[u8]) {
+        if rel_path.ends_with(".html") {
+            self.collector.scan_html(rel_path, &String::from_utf8_lossy(data));
+        } else if rel_path.ends_with(".css") {
+            self.collector.scan_css(&String::from_utf8_lossy(data));
+        }
+    }
+}
+
+impl OutputSink for CspSink<'_> {
+    fn prepare(&mut self) -> io::Result<()> {
+        self.inner.prepare()
+    }
+
+    fn write(&mut self, rel_path: &str, data: &[u8]) -> io::Result<()> {
+        self.scan(rel_path, data);
+        self.inner.write(rel_path, data)
+    }
+
+    // Copied stylesheets may reference fonts and images on other origins
+    fn copy(&mut self, src: &Path, rel_path: &str) -> io::Result<()> {
+        if rel_path.ends_with(".css") {
+            let data = fs::read(src)?;
+            self.scan(rel_path, &data);
+        }
+        self.inner.copy(src, rel_path)
+    }
+
+    fn location(&self, rel_path: &str) -> PathBuf {
+        self.inner.location(rel_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn directive<'a>(policy: &'a str, name: &str) -> &'a str {
+        policy.split("; ").find(|d| d.split(' ').next() == Some(name)).unwrap_or_default()
+    }
+
+    #[test]
+    fn origins() {
+        assert_eq!(url_origin("HTTPS://CDN.Example.com:8443/lib.js").as_deref(), Some("https://cdn.example.com:8443"));
+        assert_eq!(url_origin("http://host?x=1").as_deref(), Some("http://host"));
+        assert_eq!(url_origin("//cdn.example.com/x.js").as_deref(), Some("cdn.example.com"));
+        assert_eq!(url_origin("/local.js"), None);
+        assert_eq!(url_origin("mailto:a@example.com"), None);
+        assert_eq!(url_origin("ftp://example.com/x"), None);
+        assert_eq!(url_origin("https://"), None);
+    }
+
+    #[test]
+    fn default_policy() {
+        assert_eq!(
+            CspCollector::new("https://blog.example.com/").policy(),
+            "default-src 'self'; script-src 'self'; style-src 'self'; img-src 'self'; font-src 'self'; \
+             form-action 'self'; object-src 'none'; base-uri 'self'"
+        );
+    }
+
+    #[test]
+    fn html_sources() {
+        let mut collector = CspCollector::new("https://blog.example.com/");
+        collector.scan_html("index.html", concat!(
+            r#"<script src="https://cdn.example.com:8443/lib.js"></script>"#,
+            r#"<script src="//cdn2.example.com/a.js"></script>"#,
+            r#"<script src="/local.js"></script>"#,
+            r#"<img src="https://blog.example.com/a.png" srcset="//blog.example.com/b.png 2x">"#,
+            r#"<img src="data:image/png;base64,AAAA">"#,
+            r#"<script type="application/ld+json">{"a": 1}</script>"#,
+            r#"<script>console.log(1)</script>"#,
+            r#"<!-- <iframe src="https://video.example.com/x"></iframe> -->"#,
+        ));
+        let policy = collector.policy();
+
+        assert_eq!(
+            directive(&policy, "script-src"),
+            format!("script-src 'self' {} cdn2.example.com https://cdn.example.com:8443", hash_source(b"console.log(1)"))
+        );
+        assert_eq!(directive(&policy, "img-src"), "img-src 'self' data:");
+        assert_eq!(directive(&policy, "frame-src"), "");
+        assert!(collector.inline_handler_pages().is_empty());
+    }
+
+    #[test]
+    fn inline_styles_and_handlers() {
+        let mut collector = CspCollector::new("https://blog.example.com");
+        collector.scan_html("a.html", "<style>body{}</style>");
+        assert_eq!(directive(&collector.policy(), "style-src"), format!("style-src 'self' {}", hash_source(b"body{}")));
+
+        // Style attributes need 'unsafe-inline', which hashes would disable
+        collector.scan_html("b.html", r#"<p style="color: red">x</p><button onclick="go()">go</button>"#);
+        collector.scan_html("c.html", r#"<a href="JavaScript:void(0)">x</a>"#);
+        assert_eq!(directive(&collector.policy(), "style-src"), "style-src 'self' 'unsafe-inline'");
+        assert_eq!(collector.inline_handler_pages(), ["b.html", "c.html"]);
+    }
+
+    #[test]
+    fn css_sources() {
+        let mut collector = CspCollector::new("https://blog.example.com");
+        collector.scan_css(concat!(
+            r#"@import "https://fonts.example.com/css"; @import url(https://other.example.com/x.css);"#,
+            r#"body { background: url('https://img.example.com/bg.png'); }"#,
+            r#"@font-face { src: url(/f.woff2), url("https://fonts.example.com/f.woff2?v=1"); }"#,
+            r#".icon { background: url(data:image/svg+xml;utf8,x); }"#,
+        ));
+        let policy = collector.policy();
+
+        assert_eq!(directive(&policy, "style-src"), "style-src 'self' https://fonts.example.com https://other.example.com");
+        assert_eq!(directive(&policy, "img-src"), "img-src 'self' data: https://img.example.com");
+        assert_eq!(directive(&policy, "font-src"), "font-src 'self' https://fonts.example.com");
+    }
+}
@@ -1,9 +1,16 @@
 // src/utils/filters.rs
 
-use chrono::{DateTime, FixedOffset, NaiveDate};
-use minijinja::{Environment, Value, Error, ErrorKind};
+use std::sync::Arc;
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Timelike, Utc};
+use minijinja::{Environment, State, Value, Error, ErrorKind};
 use minijinja::value::Kwargs;
 
+use crate::config::blogconfig::SiteTimezone;
+use crate::utils::locale;
+use crate::utils::markdown::MarkdownRenderer;
+use crate::utils::text;
+
 //------------------------------------------------------------------------------
 // slugify
 //------------------------------------------------------------------------------
@@ -57,21 +64,29 @@ pub fn filter_slugify(value: Value) -> Result<Value, Error> {
 /// `chrono::DateTime<FixedOffset>`, or plain date string "2024-01-15".
 ///
 /// `fmt` defaults to `"%Y-%m-%d"` if not provided.
-pub fn filter_date(value: Value, kwargs: Kwargs) -> Result<Value, Error> {
+/// `locale` (e.g., "ko", "fr") localizes %B, %b, %A, %a and %p; defaults to `site.language`.
+pub fn filter_date(state: &State, value: Value, kwargs: Kwargs) -> Result<Value, Error> {
     let s = value.as_str().ok_or_else(|| {
         Error::new(ErrorKind::InvalidOperation, "date: expected a string value")
     })?;
 
     let fmt = kwargs.get::<Option<String>>("fmt")?.unwrap_or_else(|| "%Y-%m-%d".to_string());
+    let locale = kwargs.get::<Option<String>>("locale")?.unwrap_or_else(|| site_language(state));
     kwargs.assert_all_used()?;
 
+    let names = locale::date_names(&locale);
+
     // 1) Try RFC3339 (DateTime<FixedOffset>) — primary path
     if let Ok(dt) = DateTime::<FixedOffset>::parse_from_rfc3339(s) {
+        let fmt = locale::localize_format(&fmt, names, dt.month0() as usize,
+            dt.weekday().num_days_from_monday() as usize, Some(dt.hour()));
         return Ok(Value::from(dt.format(&fmt).to_string()));
     }
 
     // 2) Fallback: plain date "YYYY-MM-DD"
     if let Ok(nd) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        let fmt = locale::localize_format(&fmt, names, nd.month0() as usize,
+            nd.weekday().num_days_from_monday() as usize, None);
         return Ok(Value::from(nd.format(&fmt).to_string()));
     }
 
@@ -81,6 +96,229 @@ pub fn filter_date(value: Value, kwargs: Kwargs) -> Result<Value, Error> {
     ))
 }
 
+//------------------------------------------------------------------------------
+// relative_date
+//------------------------------------------------------------------------------
+
+/// minijinja filter: `{{ post.date | relative_date }}` → "3 days ago", "3일 전"
+///
+/// Relative to the build time. `locale` defaults to `site.language`; plain dates ("2024-01-15")
+/// are midnight in `site.timezone`.
+pub fn filter_relative_date(
+    state: &State,
+    value: Value,
+    kwargs: Kwargs,
+    now: DateTime<Utc>,
+    timezone: &SiteTimezone,
+) -> Result<Value, Error> {
+    let s = value.as_str().ok_or_else(|| {
+        Error::new(ErrorKind::InvalidOperation, "relative_date: expected a string value")
+    })?;
+
+    let locale = kwargs.get::<Option<String>>("locale")?.unwrap_or_else(|| site_language(state));
+    kwargs.assert_all_used()?;
+
+    let timestamp = date_timestamp(s, timezone).ok_or_else(|| Error::new(
+        ErrorKind::InvalidOperation,
+        format!("relative_date: cannot parse '{}' as a date/datetime", s),
+    ))?;
+
+    Ok(Value::from(locale::relative_time(now.timestamp() - timestamp, &locale)))
+}
+
+// RFC 3339 datetime, or "YYYY-MM-DD" at midnight in the site timezone
+fn date_timestamp(s: &str, timezone: &SiteTimezone) -> Option<i64> {
+    if let Ok(dt) = DateTime::<FixedOffset>::parse_from_rfc3339(s) {
+        return Some(dt.timestamp());
+    }
+    let nd = NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()?;
+    Some(timezone.localize(&nd.and_hms_opt(0, 0, 0)?).timestamp())
+}
+
+// `site.language` of the render context ("en" if missing)
+pub(crate) fn site_language(state: &State) -> String {
+    state.lookup("site")
+        .and_then(|site| site.get_attr("language").ok())
+        .and_then(|language| language.as_str().map(|s| s.to_string()))
+        .unwrap_or_else(|| "en".to_string())
+}
+
+//------------------------------------------------------------------------------
+// Text & HTML
+//------------------------------------------------------------------------------
+
+fn expect_str<'a>(name: &str, value: &'a Value) -> Result<&'a str, Error> {
+    value.as_str().ok_or_else(|| {
+        Error::new(ErrorKind::InvalidOperation, format!("{}: expected a string value", name))
+    })
+}
+
+/// minijinja filter: `{{ text | markdown }}` or `{{ text | markdown(inline=true) }}`
+///
+/// Renders with the site's comrak options. `inline` drops the wrapping `<p>` of a single paragraph.
+pub fn filter_markdown(renderer: &MarkdownRenderer, value: Value, kwargs: Kwargs) -> Result<Value, Error> {
+    let s = expect_str("markdown", &value)?;
+    let inline = kwargs.get::<Option<bool>>("inline")?.unwrap_or(false);
+    kwargs.assert_all_used()?;
+
+    let html = renderer.render(s);
+    let html = if inline {
+        let trimmed = html.trim();
+        match trimmed.strip_prefix("<p>").and_then(|rest| rest.strip_suffix("</p>")) {
+            Some(inner) if !inner.contains("<p>") => inner.to_string(),
+            _ => html,
+        }
+    } else {
+        html
+    };
+
+    Ok(Value::from_safe_string(html))
+}
+
+/// minijinja filter: `{{ post.content_html | truncate_html(200) }}` or `truncate_html(200, end="...")`
+///
+/// Cuts after `length` visible characters (default 200) and closes the open tags.
+pub fn filter_truncate_html(value: Value, length: Option<usize>, kwargs: Kwargs) -> Result<Value, Error> {
+    let s = expect_str("truncate_html", &value)?;
+    let end = kwargs.get::<Option<String>>("end")?.unwrap_or_else(|| "…".to_string());
+    kwargs.assert_all_used()?;
+
+    Ok(Value::from_safe_string(text::truncate_html(s, length.unwrap_or(200), &end)))
+}
+
+/// minijinja filter: `{{ post.content_html | striptags }}` (e.g., meta descriptions)
+pub fn filter_striptags(value: Value) -> Result<Value, Error> {
+    Ok(Value::from(text::strip_tags(expect_str("striptags", &value)?)))
+}
+
+/// minijinja filter: `{{ post.title | xml_escape }}` (feeds and other XML templates)
+pub fn filter_xml_escape(value: Value) -> Result<Value, Error> {
+    let s = match value.as_str() {
+        Some(s) => s.to_string(),
+        None => value.to_string(),
+    };
+    Ok(Value::from_safe_string(text::escape_xml(&s)))
+}
+
+/// minijinja filter: `{{ value | json_encode }}` or `json_encode(pretty=true)`
+///
+/// `<`, `>`, `&` and `'` are escaped as \uXXXX so the output is safe inside `<script>` and attributes.
+pub fn filter_json_encode(value: Value, kwargs: Kwargs) -> Result<Value, Error> {
+    let pretty = kwargs.get::<Option<bool>>("pretty")?.unwrap_or(false);
+    kwargs.assert_all_used()?;
+
+    let json = if pretty { serde_json::to_string_pretty(&value) } else { serde_json::to_string(&value) }
+        .map_err(|e| Error::new(ErrorKind::InvalidOperation, format!("json_encode: {}", e)))?;
+
+    Ok(Value::from_safe_string(json
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026")
+        .replace('\'', "\\u0027")))
+}
+
+/// minijinja filter: `{{ post.content_html | word_count }}`
+///
+/// Tags are ignored; each Chinese/Japanese character counts as one word.
+pub fn filter_word_count(value: Value) -> Result<Value, Error> {
+    let s = expect_str("word_count", &value)?;
+    Ok(Value::from(text::count_words(&text::strip_tags(s)).total()))
+}
+
+/// minijinja filter: `{{ post.content_html | reading_time }}` (minutes, at least 1)
+///
+/// `wpm`: words per minute (default 200), `cpm`: Chinese/Japanese characters per minute (default 500)
+pub fn filter_reading_time(value: Value, kwargs: Kwargs) -> Result<Value, Error> {
+    let s = expect_str("reading_time", &value)?;
+    let wpm = kwargs.get::<Option<usize>>("wpm")?.unwrap_or(200).max(1);
+    let cpm = kwargs.get::<Option<usize>>("cpm")?.unwrap_or(500).max(1);
+    kwargs.assert_all_used()?;
+
+    let count = text::count_words(&text::strip_tags(s));
+    let minutes = count.words as f64 / wpm as f64 + count.cjk_chars as f64 / cpm as f64;
+
+    Ok(Value::from((minutes.ceil() as usize).max(1)))
+}
+
+//------------------------------------------------------------------------------
+// Lists (posts, data files)
+//------------------------------------------------------------------------------
+
+// Dotted attribute lookup ("extra.featured"), undefined if missing
+fn attr_path(value: &Value, path: &str) -> Value {
+    path.split('.').fold(value.clone(), |v, key| v.get_attr(key).unwrap_or(Value::UNDEFINED))
+}
+
+fn expect_items(name: &str, value: &Value) -> Result<Vec<Value>, Error> {
+    value.try_iter()
+        .map(|iter| iter.collect())
+        .map_err(|_| Error::new(ErrorKind::InvalidOperation, format!("{}: expected a list", name)))
+}
+
+/// minijinja filter: `{{ posts | where("extra.featured") }}` or `where("language", "ko")`
+///
+/// Keeps items whose attribute is truthy, equals the value, or (for lists) contains the value.
+pub fn filter_where(value: Value, attribute: String, expected: Option<Value>) -> Result<Value, Error> {
+    let items = expect_items("where", &value)?;
+
+    let matched: Vec<Value> = items.into_iter()
+        .filter(|item| {
+            let attr = attr_path(item, &attribute);
+            match &expected {
+                None => attr.is_true(),
+                Some(expected) if attr == *expected => true,
+                Some(expected) => attr.as_str().is_none()
+                    && attr.try_iter().is_ok_and(|mut values| values.any(|v| v == *expected)),
+            }
+        })
+        .collect();
+
+    Ok(Value::from(matched))
+}
+
+/// minijinja filter: `{{ posts | sort_by("title") }}` or `sort_by("updated", reverse=true)`
+///
+/// Items without the attribute come last.
+pub fn filter_sort_by(value: Value, attribute: String, kwargs: Kwargs) -> Result<Value, Error> {
+    let reverse = kwargs.get::<Option<bool>>("reverse")?.unwrap_or(false);
+    kwargs.assert_all_used()?;
+
+    let mut keyed: Vec<(Value, Value)> = expect_items("sort_by", &value)?
+        .into_iter()
+        .map(|item| (attr_path(&item, &attribute), item))
+        .collect();
+
+    keyed.sort_by(|(a, _), (b, _)| {
+        let missing = |v: &Value| v.is_undefined() || v.is_none();
+        match (missing(a), missing(b)) {
+            (false, false) if reverse => b.cmp(a),
+            (false, false) => a.cmp(b),
+            (a_missing, b_missing) => a_missing.cmp(&b_missing),
+        }
+    });
+
+    Ok(Value::from(keyed.into_iter().map(|(_, item)| item).collect::<Vec<_>>()))
+}
+
+/// minijinja filter: `{% for group in posts | group_by("extra.series") %}{{ group.key }}: {{ group.items | length }}{% endfor %}`
+///
+/// Groups in order of first appearance.
+pub fn filter_group_by(value: Value, attribute: String) -> Result<Value, Error> {
+    let mut groups: Vec<(Value, Vec<Value>)> = Vec::new();
+
+    for item in expect_items("group_by", &value)? {
+        let key = attr_path(&item, &attribute);
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, items)) => items.push(item),
+            None => groups.push((key, vec![item])),
+        }
+    }
+
+    Ok(Value::from(groups.into_iter()
+        .map(|(key, items)| minijinja::context! { key => key, items => items })
+        .collect::<Vec<_>>()))
+}
+
 //------------------------------------------------------------------------------
 // Register all filters
 //------------------------------------------------------------------------------
@@ -88,7 +326,49 @@ pub fn filter_date(value: Value, kwargs: Kwargs) -> Result<Value, Error> {
 /// Register all custom filters onto the given minijinja `Environment`.
 ///
 /// Call this once after `Environment::new()` / `set_loader()`.
-pub fn register_all(env: &mut Environment) {
+pub fn register_all(env: &mut Environment, timezone: SiteTimezone) {
     env.add_filter("slugify", filter_slugify);
     env.add_filter("date", filter_date);
+
+    let build_time = Utc::now();
+    env.add_filter("relative_date", move |state: &State, value: Value, kwargs: Kwargs| {
+        filter_relative_date(state, value, kwargs, build_time, &timezone)
+    });
+
+    let renderer = Arc::new(MarkdownRenderer::new());
+    env.add_filter("markdown", move |value: Value, kwargs: Kwargs| filter_markdown(&renderer, value, kwargs));
+    env.add_filter("truncate_html", filter_truncate_html);
+    env.add_filter("striptags", filter_striptags);
+    env.add_filter("xml_escape", filter_xml_escape);
+    env.add_filter("json_encode", filter_json_encode);
+    env.add_filter("word_count", filter_word_count);
+    env.add_filter("reading_time", filter_reading_time);
+    env.add_filter("where", filter_where);
+    env.add_filter("sort_by", filter_sort_by);
+    env.add_filter("group_by", filter_group_by);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_dates_use_the_site_timezone() {
+        let seoul = SiteTimezone::parse("Asia/Seoul").unwrap();
+        let new_york = SiteTimezone::parse("-05:00").unwrap();
+        let utc = SiteTimezone::parse("+00:00").unwrap();
+
+        // 2024-01-15T00:00:00Z
+        assert_eq!(date_timestamp("2024-01-15", &utc), Some(1_705_276_800));
+        assert_eq!(date_timestamp("2024-01-15", &seoul), Some(1_705_276_800 - 9 * 3600));
+        assert_eq!(date_timestamp("2024-01-15", &new_york), Some(1_705_276_800 + 5 * 3600));
+    }
+
+    #[test]
+    fn datetimes_keep_their_offset() {
+        let seoul = SiteTimezone::parse("Asia/Seoul").unwrap();
+        assert_eq!(date_timestamp("2024-01-15T09:00:00+09:00", &seoul), Some(1_705_276_800));
+        assert_eq!(date_timestamp("2024-01-15T00:00:00Z", &seoul), Some(1_705_276_800));
+        assert_eq!(date_timestamp("yesterday", &seoul), None);
+    }
 }
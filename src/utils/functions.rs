@@ -0,0 +1,234 @@
+// src/utils/functions.rs
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use minijinja::{Environment, State, Value, Error, ErrorKind};
+use minijinja::value::Kwargs;
+use serde::Serialize;
+
+use crate::content::content_source::ContentSource;
+use crate::context::{context_builder, ContentContext, PostListItem, TaxonomyItem};
+use crate::utils::filters::{site_language, slugify};
+
+//------------------------------------------------------------------------------
+// Query data (per language)
+//------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct LanguageQueries {
+    posts: Vec<ContentContext>,     // Newest first
+    pages: Vec<ContentContext>,
+    paths: BTreeMap<String, ContentRef>,
+    taxonomies: BTreeMap<String, Vec<TaxonomyItem>>,
+}
+
+#[derive(Debug, Copy, Clone)]
+enum ContentRef {
+    Post(usize),
+    Page(usize),
+}
+
+/// Rendered contents of every language, queried by the template functions
+#[derive(Debug, Default)]
+pub struct SiteQueries {
+    languages: Vec<(String, LanguageQueries)>,  // Default language first
+}
+
+/// Term of `get_taxonomy()`: a `TaxonomyItem` with its posts
+#[derive(Debug, Serialize)]
+struct TaxonomyTerm<'a> {
+    label: &'a str,
+    url: &'a str,
+    count: usize,
+    posts: Vec<PostListItem>,
+}
+
+impl SiteQueries {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the contents of one language (`*_sources` are parallel to the contexts)
+    pub fn add_language(
+        &mut self,
+        language: &str,
+        posts: (&[ContentContext], &[&ContentSource]),
+        pages: (&[ContentContext], &[&ContentSource]),
+        taxonomies: &BTreeMap<String, Vec<TaxonomyItem>>,
+    ) {
+        let mut paths = BTreeMap::new();
+        let entries = posts.0.iter().zip(posts.1).enumerate().map(|(i, entry)| (ContentRef::Post(i), entry))
+            .chain(pages.0.iter().zip(pages.1).enumerate().map(|(i, entry)| (ContentRef::Page(i), entry)));
+
+        for (content_ref, (context, source)) in entries {
+            // Source path ("pages/about") and translation key, then URL ("/pages/about/")
+            for key in [source.meta.id.0.as_str(), context.translation_key.as_str(), context.url.as_str()] {
+                paths.entry(normalize_path(key)).or_insert(content_ref);
+            }
+        }
+
+        self.languages.push((language.to_string(), LanguageQueries {
+            posts: posts.0.to_vec(),
+            pages: pages.0.to_vec(),
+            paths,
+            taxonomies: taxonomies.clone(),
+        }));
+    }
+
+    // Contents of the language being rendered (`site.language`), or the default language
+    fn language(&self, state: &State) -> Result<&LanguageQueries, Error> {
+        let language = site_language(state);
+        self.languages.iter()
+            .find(|(code, _)| *code == language)
+            .or_else(|| self.languages.first())
+            .map(|(_, queries)| queries)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidOperation, "no contents to query"))
+    }
+}
+
+// "pages/about.md", "/pages/about/", "posts/my-post/index" -> "pages/about", "posts/my-post"
+fn normalize_path(path: &str) -> String {
+    let path = path.trim().trim_matches('/');
+    let path = path.strip_suffix(".md").unwrap_or(path);
+    let path = path.strip_suffix("/index").unwrap_or(path);
+    path.to_string()
+}
+
+//------------------------------------------------------------------------------
+// get_posts
+//------------------------------------------------------------------------------
+
+/// minijinja function: `get_posts(taxonomy="tags", term="rust", limit=5, sort="date")`
+///
+/// Returns `PostListItem`s of the current language.
+///   - `taxonomy` / `term`: only posts with the term (compared by slug)
+///   - `sort`: "date" (newest first, default), "date_asc", "updated", "title"
+fn get_posts(queries: &SiteQueries, state: &State, kwargs: Kwargs) -> Result<Value, Error> {
+    let taxonomy = kwargs.get::<Option<String>>("taxonomy")?;
+    let term = kwargs.get::<Option<String>>("term")?;
+    let limit = kwargs.get::<Option<usize>>("limit")?;
+    let sort = kwargs.get::<Option<String>>("sort")?.unwrap_or_else(|| "date".to_string());
+    kwargs.assert_all_used()?;
+
+    let language = queries.language(state)?;
+    let mut posts: Vec<&ContentContext> = language.posts.iter().collect();
+
+    match (&taxonomy, &term) {
+        (Some(taxonomy), term) => {
+            if !language.taxonomies.contains_key(taxonomy) {
+                return Err(Error::new(ErrorKind::InvalidOperation,
+                    format!("get_posts: unknown taxonomy '{}' (not defined in theme.yaml)", taxonomy)));
+            }
+
+            let term_slug = term.as_deref().map(slugify);
+            posts.retain(|post| {
+                post.taxonomies.as_ref()
+                    .and_then(|taxonomies| taxonomies.get(taxonomy))
+                    .is_some_and(|items| match &term_slug {
+                        Some(slug) => items.iter().any(|item| slugify(&item.label) == *slug),
+                        None => !items.is_empty(),
+                    })
+            });
+        }
+        (None, Some(_)) => {
+            return Err(Error::new(ErrorKind::InvalidOperation, "get_posts: `term` requires `taxonomy`"));
+        }
+        (None, None) => {}
+    }
+
+    match sort.as_str() {
+        "date" => {}
+        "date_asc" => posts.reverse(),
+        "updated" => posts.sort_by_key(|post| Reverse(post.updated.unwrap_or(post.date))),
+        "title" => posts.sort_by(|a, b| a.title.cmp(&b.title)),
+        other => {
+            return Err(Error::new(ErrorKind::InvalidOperation,
+                format!("get_posts: unknown sort '{}' (expected date, date_asc, updated or title)", other)));
+        }
+    }
+
+    let items: Vec<PostListItem> = posts.into_iter()
+        .take(limit.unwrap_or(usize::MAX))
+        .map(context_builder::build_post_list_item)
+        .collect();
+
+    Ok(Value::from_serialize(&items))
+}
+
+//------------------------------------------------------------------------------
+// get_page
+//------------------------------------------------------------------------------
+
+/// minijinja function: `get_page(path="pages/about.md")`
+///
+/// Returns the `ContentContext` of a post or page of the current language,
+/// by source path under `content/` (with or without ".md") or by URL.
+fn get_page(queries: &SiteQueries, state: &State, kwargs: Kwargs) -> Result<Value, Error> {
+    let path: String = kwargs.get("path")?;
+    kwargs.assert_all_used()?;
+
+    let language = queries.language(state)?;
+    let context = match language.paths.get(&normalize_path(&path)) {
+        Some(ContentRef::Post(i)) => &language.posts[*i],
+        Some(ContentRef::Page(i)) => &language.pages[*i],
+        None => {
+            return Err(Error::new(ErrorKind::InvalidOperation,
+                format!("get_page: no content found at '{}'", path)));
+        }
+    };
+
+    Ok(Value::from_serialize(context))
+}
+
+//------------------------------------------------------------------------------
+// get_taxonomy
+//------------------------------------------------------------------------------
+
+/// minijinja function: `get_taxonomy(name="tags")`
+///
+/// Returns the terms of a taxonomy (`TaxonomyItem` fields) with their `posts` (`PostListItem`s).
+fn get_taxonomy(queries: &SiteQueries, state: &State, kwargs: Kwargs) -> Result<Value, Error> {
+    let name: String = kwargs.get("name")?;
+    kwargs.assert_all_used()?;
+
+    let language = queries.language(state)?;
+    let items = language.taxonomies.get(&name).ok_or_else(|| {
+        Error::new(ErrorKind::InvalidOperation,
+            format!("get_taxonomy: unknown taxonomy '{}' (not defined in theme.yaml)", name))
+    })?;
+
+    let terms: Vec<TaxonomyTerm> = items.iter()
+        .map(|item| TaxonomyTerm {
+            label: &item.label,
+            url: &item.url,
+            count: item.count,
+            posts: language.posts.iter()
+                .filter(|post| post.taxonomies.as_ref()
+                    .and_then(|taxonomies| taxonomies.get(&name))
+                    .is_some_and(|post_items| post_items.iter().any(|i| i.url == item.url)))
+                .map(context_builder::build_post_list_item)
+                .collect(),
+        })
+        .collect();
+
+    Ok(Value::from_serialize(&terms))
+}
+
+//------------------------------------------------------------------------------
+// Register all functions
+//------------------------------------------------------------------------------
+
+pub fn register_all(env: &mut Environment, queries: SiteQueries) {
+    let queries = Arc::new(queries);
+
+    let q = Arc::clone(&queries);
+    env.add_function("get_posts", move |state: &State, kwargs: Kwargs| get_posts(&q, state, kwargs));
+
+    let q = Arc::clone(&queries);
+    env.add_function("get_page", move |state: &State, kwargs: Kwargs| get_page(&q, state, kwargs));
+
+    let q = Arc::clone(&queries);
+    env.add_function("get_taxonomy", move |state: &State, kwargs: Kwargs| get_taxonomy(&q, state, kwargs));
+}
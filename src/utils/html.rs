@@ -0,0 +1,80 @@
+// src/utils/html.rs
+
+/// An opening tag found in generated HTML
+pub struct HtmlTag {
+    pub name: String,                       // Lowercase
+    pub attributes: Vec<(String, String)>,  // Lowercase name, raw value
+    pub end: usize,                         // Offset after '>'
+}
+
+impl HtmlTag {
+    /// Raw (still escaped) value of an attribute
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str())
+    }
+}
+
+/// Parses the tag starting at `start` (just after '<'); `None` for closing tags, comments and stray '<'
+pub fn parse_tag(html: &str, start: usize) -> Option<HtmlTag> {
+    let bytes = html.as_bytes();
+    let len = bytes.len();
+    if !bytes.get(start)?.is_ascii_alphabetic() {
+        return None;
+    }
+
+    let mut i = start;
+    while i < len && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'-') {
+        i += 1;
+    }
+    let name = html[start..i].to_ascii_lowercase();
+
+    let mut attributes = Vec::new();
+    loop {
+        while i < len && (bytes[i].is_ascii_whitespace() || bytes[i] == b'/') {
+            i += 1;
+        }
+        if i >= len {
+            return Some(HtmlTag { name, attributes, end: len });
+        }
+        if bytes[i] == b'>' {
+            return Some(HtmlTag { name, attributes, end: i + 1 });
+        }
+
+        let name_start = i;
+        while i < len && !bytes[i].is_ascii_whitespace() && !matches!(bytes[i], b'=' | b'>' | b'/') {
+            i += 1;
+        }
+        if i == name_start {
+            i += 1;
+            continue;
+        }
+        let attribute_name = html[name_start..i].to_ascii_lowercase();
+
+        let mut j = i;
+        while j < len && bytes[j].is_ascii_whitespace() {
+            j += 1;
+        }
+        let mut value = "";
+        if j < len && bytes[j] == b'=' {
+            j += 1;
+            while j < len && bytes[j].is_ascii_whitespace() {
+                j += 1;
+            }
+            if j < len && (bytes[j] == b'"' || bytes[j] == b'\'') {
+                let quote = bytes[j] as char;
+                let value_end = html[j + 1..].find(quote).map_or(len, |end| j + 1 + end);
+                value = &html[j + 1..value_end];
+                i = (value_end + 1).min(len);
+            } else {
+                // Unquoted values (minified HTML) end at whitespace or '>'
+                let value_start = j;
+                while j < len && !bytes[j].is_ascii_whitespace() && bytes[j] != b'>' {
+                    j += 1;
+                }
+                value = &html[value_start..j];
+                i = j;
+            }
+        }
+        attributes.push((attribute_name, value.to_string()));
+    }
+}
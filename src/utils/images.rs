@@ -0,0 +1,854 @@
+// src/utils/images.rs
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, BufReader, Cursor};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use image::codecs::avif::AvifEncoder;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::{CompressionType, FilterType as PngFilterType, PngEncoder};
+use image::imageops::FilterType;
+use image::metadata::Orientation;
+use image::{DynamicImage, ImageDecoder, ImageReader};
+use minijinja::{Environment, Error, ErrorKind, Value};
+use minijinja::value::Kwargs;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::config::blogconfig::{ImageFormat, ImagesConfig};
+use crate::utils::html::{HtmlTag, parse_tag};
+use crate::utils::url_helpers;
+
+// rav1e speed (0 = slowest, 10 = fastest); slower settings barely shrink photos further
+const AVIF_SPEED: u8 = 8;
+
+#[derive(Error, Debug)]
+pub enum ImageError {
+    #[error("IO error\n  Path: {path}\n  Reason: {source}")]
+    Io { path: PathBuf, source: io::Error },
+
+    #[error("Failed to decode image\n  Path: {path}\n  Reason: {reason}")]
+    DecodeError { path: PathBuf, reason: String },
+
+    #[error("Failed to encode image\n  Path: {path}\n  Reason: {reason}")]
+    EncodeError { path: PathBuf, reason: String },
+
+    #[error("Image not found\n  Path: {path}\n  Expected: a file of content/images/ or a content bundle image")]
+    ImageNotFound { path: String },
+}
+
+// Encodings of generated files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Encoding {
+    Jpeg,
+    Png,
+    Webp,
+    Avif,
+}
+
+impl Encoding {
+    // Source images that can be processed (GIF and SVG are left untouched)
+    fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "jpg" | "jpeg" => Some(Encoding::Jpeg),
+            "png" => Some(Encoding::Png),
+            "webp" => Some(Encoding::Webp),
+            _ => None,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Encoding::Jpeg => "jpg",
+            Encoding::Png => "png",
+            Encoding::Webp => "webp",
+            Encoding::Avif => "avif",
+        }
+    }
+
+    fn mime_type(self) -> &'static str {
+        match self {
+            Encoding::Jpeg => "image/jpeg",
+            Encoding::Png => "image/png",
+            Encoding::Webp => "image/webp",
+            Encoding::Avif => "image/avif",
+        }
+    }
+}
+
+impl From<ImageFormat> for Encoding {
+    fn from(format: ImageFormat) -> Self {
+        match format {
+            ImageFormat::Avif => Encoding::Avif,
+            ImageFormat::Webp => Encoding::Webp,
+        }
+    }
+}
+
+/// A generated file (e.g., "photo-960w-1a2b3c4d.webp", next to the source image)
+#[derive(Debug, Clone)]
+pub struct ImageVariant {
+    pub file_name: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+// Every variant of a source image: modern formats first, the original format last (used by <img>)
+#[derive(Debug)]
+struct ProcessedImage {
+    variants: Vec<(Encoding, Vec<ImageVariant>)>,
+}
+
+/// Generated files to write into the site
+pub struct ImageOutputs {
+    pub files: Vec<(String, PathBuf)>,  // Site-relative output path -> cached file
+    pub generated: usize,               // Encoded by this build
+    pub cached: usize,                  // Reused from the cache directory
+}
+
+#[derive(Default)]
+struct PipelineState {
+    processed: BTreeMap<String, Arc<ProcessedImage>>,           // Keyed by site URL path
+    resized: BTreeMap<(String, u32, Encoding), String>,         // resize_image results
+    dimensions: BTreeMap<String, Option<(u32, u32)>>,           // Header dimensions of unprocessed images
+    outputs: BTreeMap<String, PathBuf>,
+    generated: usize,
+    cached: usize,
+}
+
+//------------------------------------------------------------------------------
+// ImagePipeline - dimensions, lazy-loading and captions of markdown images, plus
+// resized / converted variants (cached between builds) when enabled
+//------------------------------------------------------------------------------
+pub struct ImagePipeline {
+    enabled: bool,                          // Variants are generated
+    lazy: bool,
+    captions: bool,
+    widths: Vec<u32>,
+    formats: Vec<Encoding>,
+    quality: u8,
+    sizes: String,
+    cache_dir: PathBuf,
+    site_path: String,                      // "" or "/blog", stripped from absolute image URLs
+    sources: BTreeMap<String, PathBuf>,     // Site URL path ("/posts/hello/photo.jpg") -> source file
+    state: Mutex<PipelineState>,
+}
+
+impl ImagePipeline {
+    pub fn new(project_path: &Path, config: &ImagesConfig, site_path: &str) -> Self {
+        let mut widths = config.widths.clone();
+        widths.sort_unstable();
+        widths.dedup();
+
+        let mut formats: Vec<Encoding> = config.formats.iter().map(|f| Encoding::from(*f)).collect();
+        formats.sort();
+        formats.dedup();
+        formats.reverse();      // AVIF before WebP: browsers pick the first supported <source>
+
+        let site_path = site_path.trim().trim_matches('/');
+
+        let mut pipeline = ImagePipeline {
+            enabled: config.enabled,
+            lazy: config.lazy,
+            captions: config.captions,
+            widths,
+            formats,
+            quality: config.quality,
+            sizes: config.sizes.clone(),
+            cache_dir: project_path.join(&config.cache_dir),
+            site_path: if site_path.is_empty() { String::new() } else { format!("/{}", site_path) },
+            sources: BTreeMap::new(),
+            state: Mutex::new(PipelineState::default()),
+        };
+
+        // content/images/ is published as /images/
+        let images_dir = project_path.join("content").join("images");
+        pipeline.add_dir(&images_dir, "/images");
+        pipeline
+    }
+
+    fn add_dir(&mut self, dir: &Path, url_prefix: &str) {
+        let Ok(entries) = fs::read_dir(dir) else { return };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let url = format!("{}/{}", url_prefix, entry.file_name().to_string_lossy());
+            if path.is_dir() {
+                self.add_dir(&path, &url);
+            } else if path.is_file() {
+                self.sources.insert(url, path);
+            }
+        }
+    }
+
+    /// Registers the images of a content (copied next to its `index.html`)
+    pub fn add_content_images(&mut self, page_url: &str, images: &[PathBuf]) {
+        for image in images {
+            if let Some(file_name) = image.file_name() {
+                let url = format!("{}/{}", page_url.trim_end_matches('/'), file_name.to_string_lossy());
+                self.sources.insert(url, image.clone());
+            }
+        }
+    }
+
+    /// Rewrites the `<img>` tags of rendered markdown: `width` / `height` from the image file,
+    /// lazy-loading attributes, `<picture>` / `srcset` markup when enabled, and a
+    /// `<figure>` with the title as caption for an image alone in its paragraph
+    pub fn rewrite_html(&self, html: &str, page_url: &str) -> Result<String, ImageError> {
+        let mut out = String::with_capacity(html.len());
+        let mut pos = 0;
+
+        while let Some(offset) = html[pos..].find("<img") {
+            let start = pos + offset;
+            let Some(mut tag) = parse_tag(html, start + 1).filter(|tag| tag.name == "img") else {
+                out.push_str(&html[pos..start + 4]);
+                pos = start + 4;
+                continue;
+            };
+
+            out.push_str(&html[pos..start]);
+
+            // <p><img ... title="..." /></p> -> <figure><img ... /><figcaption>...</figcaption></figure>
+            let standalone = out.ends_with("<p>") && html[tag.end..].starts_with("</p>");
+            let caption = match tag.attributes.iter().position(|(name, _)| name == "title") {
+                Some(i) if self.captions && standalone && !tag.attributes[i].1.trim().is_empty() => {
+                    Some(tag.attributes.remove(i).1)
+                }
+                _ => None,
+            };
+
+            let markup = self.image_markup(&tag, page_url)?;
+            match caption {
+                Some(caption) => {
+                    out.truncate(out.len() - "<p>".len());
+                    out.push_str(&format!("<figure>{}<figcaption>{}</figcaption></figure>", markup, caption));
+                    pos = tag.end + "</p>".len();
+                }
+                None => {
+                    out.push_str(&markup);
+                    pos = tag.end;
+                }
+            }
+        }
+
+        out.push_str(&html[pos..]);
+        Ok(out)
+    }
+
+    /// `resize_image(path, width)`: URL of a single variant (`format`: original format by default)
+    pub fn resize(&self, path: &str, width: u32, format: Option<ImageFormat>) -> Result<String, ImageError> {
+        if !self.enabled {
+            return Ok(path.to_string());
+        }
+        let url_path = self.resolve(path, "/")
+            .ok_or_else(|| ImageError::ImageNotFound { path: path.to_string() })?;
+        let source = self.sources.get(&url_path)
+            .ok_or_else(|| ImageError::ImageNotFound { path: path.to_string() })?;
+        let original = Encoding::from_path(source).ok_or_else(|| ImageError::DecodeError {
+            path: source.clone(),
+            reason: "unsupported image format (expected JPEG, PNG or WebP)".to_string(),
+        })?;
+        let encoding = format.map(Encoding::from).unwrap_or(original);
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let key = (url_path.clone(), width, encoding);
+        let file_name = match state.resized.get(&key) {
+            Some(file_name) => file_name.clone(),
+            None => {
+                let mut image = SourceImage::read(source, &url_path)?;
+                let width = width.min(image.width);
+                let variant = self.variant(&mut image, width, encoding, &mut state)?;
+                state.resized.insert(key, variant.file_name.clone());
+                variant.file_name
+            }
+        };
+
+        Ok(format!("{}{}", url_dir(path), urlencoding::encode(&file_name)))
+    }
+
+    /// Generated files so far (call after rendering)
+    pub fn outputs(&self) -> ImageOutputs {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        ImageOutputs {
+            files: state.outputs.iter().map(|(rel, cached)| (rel.clone(), cached.clone())).collect(),
+            generated: state.generated,
+            cached: state.cached,
+        }
+    }
+
+    fn image_markup(&self, tag: &HtmlTag, page_url: &str) -> Result<String, ImageError> {
+        let src = tag.attr("src").unwrap_or_default();
+        let url_path = self.resolve(src, page_url);
+
+        // Hand-written responsive markup gets no variants
+        let processed = match &url_path {
+            Some(url_path) if self.enabled && tag.attr("srcset").is_none() => self.process(url_path)?,
+            _ => None,
+        };
+        let processed = processed.as_deref().and_then(|processed| {
+            let ((_, fallback), modern) = processed.variants.split_last()?;
+            Some((modern, fallback, fallback.last()?))
+        });
+
+        // Variants sit next to the source image, so the directory part of `src` is kept as written
+        let base = url_dir(src);
+        let srcset = |variants: &[ImageVariant]| variants.iter()
+            .map(|v| format!("{}{} {}w", base, urlencoding::encode(&v.file_name), v.width))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let sizes = self.sizes.replace('"', "&quot;");
+
+        // Attributes written by hand in the markdown (raw HTML) are kept
+        let dimensions = match processed {
+            Some((_, _, largest)) => Some((largest.width, largest.height)),
+            None if tag.attr("width").is_some() || tag.attr("height").is_some() => None,
+            None => url_path.as_deref().and_then(|url_path| self.dimensions(url_path)),
+        };
+        let replaced: &[&str] = match processed {
+            Some(_) => &["src", "srcset", "sizes", "width", "height"],
+            None => &["src"],
+        };
+
+        let mut img = format!("<img src=\"{}\"", src.replace('"', "&quot;"));
+        if let Some((_, fallback, largest)) = processed {
+            img = format!("<img src=\"{}{}\" srcset=\"{}\" sizes=\"{}\"",
+                base, urlencoding::encode(&largest.file_name), srcset(fallback), sizes);
+        }
+        if let Some((width, height)) = dimensions {
+            img.push_str(&format!(" width=\"{}\" height=\"{}\"", width, height));
+        }
+        for (name, value) in &tag.attributes {
+            if !replaced.contains(&name.as_str()) {
+                img.push_str(&format!(" {}=\"{}\"", name, value.replace('"', "&quot;")));
+            }
+        }
+        if self.lazy {
+            if tag.attr("loading").is_none() {
+                img.push_str(" loading=\"lazy\"");
+            }
+            if tag.attr("decoding").is_none() {
+                img.push_str(" decoding=\"async\"");
+            }
+        }
+        img.push_str(" />");
+
+        let Some((modern, _, _)) = processed.filter(|(modern, _, _)| !modern.is_empty()) else {
+            return Ok(img);
+        };
+
+        let mut markup = String::from("<picture>");
+        for (encoding, variants) in modern {
+            markup.push_str(&format!("<source type=\"{}\" srcset=\"{}\" sizes=\"{}\">",
+                encoding.mime_type(), srcset(variants), sizes));
+        }
+        markup.push_str(&img);
+        markup.push_str("</picture>");
+        Ok(markup)
+    }
+
+    // Displayed size of an image read from its header (None for unknown files and formats without decoder)
+    fn dimensions(&self, url_path: &str) -> Option<(u32, u32)> {
+        let source = self.sources.get(url_path)?;
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(dimensions) = state.dimensions.get(url_path) {
+            return *dimensions;
+        }
+
+        let dimensions = header_dimensions(source);
+        state.dimensions.insert(url_path.to_string(), dimensions);
+        dimensions
+    }
+
+    // Every configured width and format of an image (None for unknown or unsupported files)
+    fn process(&self, url_path: &str) -> Result<Option<Arc<ProcessedImage>>, ImageError> {
+        let Some(source) = self.sources.get(url_path) else { return Ok(None) };
+        let Some(original) = Encoding::from_path(source) else { return Ok(None) };
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(processed) = state.processed.get(url_path) {
+            return Ok(Some(Arc::clone(processed)));
+        }
+
+        let mut image = SourceImage::read(source, url_path)?;
+
+        // Configured widths narrower than the image, plus the image's own width if a wider one was asked for
+        let mut widths: Vec<u32> = self.widths.iter().copied().filter(|w| *w < image.width).collect();
+        if self.widths.iter().any(|w| *w >= image.width) {
+            widths.push(image.width);
+        }
+
+        let encodings = self.formats.iter().copied()
+            .filter(|encoding| *encoding != original)
+            .chain([original]);
+
+        let mut variants = Vec::new();
+        for encoding in encodings {
+            let mut encoded = Vec::new();
+            for width in &widths {
+                encoded.push(self.variant(&mut image, *width, encoding, &mut state)?);
+            }
+            variants.push((encoding, encoded));
+        }
+
+        let processed = Arc::new(ProcessedImage { variants });
+        state.processed.insert(url_path.to_string(), Arc::clone(&processed));
+        Ok(Some(processed))
+    }
+
+    // One resized / converted file, encoded unless the cache already has it
+    fn variant(
+        &self,
+        image: &mut SourceImage,
+        width: u32,
+        encoding: Encoding,
+        state: &mut PipelineState,
+    ) -> Result<ImageVariant, ImageError> {
+        let height = ((image.height as f64 * width as f64 / image.width as f64).round() as u32).max(1);
+        // The content hash keeps "photo.jpg" and "photo.png" of one directory apart
+        let file_name = format!("{}-{}w-{}.{}", image.stem, width, &image.hash[..8], encoding.extension());
+        let cache_path = self.cache_dir.join(format!("{}-{}-q{}.{}", image.hash, width, self.quality, encoding.extension()));
+
+        if cache_path.is_file() {
+            state.cached += 1;
+        } else {
+            let decoded = image.decoded()?;
+            let resized = if width == decoded.width() {
+                decoded.clone()
+            } else {
+                decoded.resize_exact(width, height, FilterType::Lanczos3)
+            };
+            let data = encode(&resized, encoding, self.quality)
+                .map_err(|reason| ImageError::EncodeError { path: image.path.clone(), reason })?;
+
+            fs::create_dir_all(&self.cache_dir)
+                .map_err(|e| ImageError::Io { path: self.cache_dir.clone(), source: e })?;
+            fs::write(&cache_path, data)
+                .map_err(|e| ImageError::Io { path: cache_path.clone(), source: e })?;
+            state.generated += 1;
+        }
+
+        let output = format!("{}{}", url_dir(&image.url_path), file_name);
+        state.outputs.insert(output.trim_start_matches('/').to_string(), cache_path);
+
+        Ok(ImageVariant { file_name, width, height })
+    }
+
+    // Site URL path of an image reference ("photo.jpg" on "/posts/hello/" -> "/posts/hello/photo.jpg")
+    fn resolve(&self, src: &str, page_url: &str) -> Option<String> {
+        let src = src.replace("&amp;", "&");
+        let src = src.split(['?', '#']).next().unwrap_or_default().trim();
+        if src.is_empty() || src.contains("://") || src.starts_with("//") || src.starts_with("data:") {
+            return None;
+        }
+        let src = urlencoding::decode(src).ok()?;
+
+        let path = match src.strip_prefix('/') {
+            Some(_) => {
+                let without_site_path = src.strip_prefix(self.site_path.as_str())
+                    .filter(|rest| !self.site_path.is_empty() && rest.starts_with('/'));
+                without_site_path.unwrap_or(&src).to_string()
+            }
+            None => format!("{}/{}", page_url.trim_end_matches('/'), src),
+        };
+
+        // Resolve "." and ".." segments
+        let mut segments: Vec<&str> = Vec::new();
+        for segment in path.split('/') {
+            match segment {
+                "" | "." => {}
+                ".." => { segments.pop(); }
+                _ => segments.push(segment),
+            }
+        }
+        Some(format!("/{}", segments.join("/")))
+    }
+}
+
+// "a/b/photo.jpg" -> "a/b/", "photo.jpg" -> ""
+fn url_dir(url: &str) -> &str {
+    match url.rfind('/') {
+        Some(i) => &url[..=i],
+        None => "",
+    }
+}
+
+//------------------------------------------------------------------------------
+// Decoding & encoding
+//------------------------------------------------------------------------------
+
+// A source image: dimensions are read from the header, pixels are decoded on the first cache miss
+struct SourceImage {
+    path: PathBuf,
+    url_path: String,
+    stem: String,
+    bytes: Vec<u8>,
+    hash: String,       // Content hash, part of the cache file names
+    width: u32,         // After EXIF orientation
+    height: u32,
+    decoded: Option<DynamicImage>,
+}
+
+impl SourceImage {
+    fn read(path: &Path, url_path: &str) -> Result<Self, ImageError> {
+        let bytes = fs::read(path)
+            .map_err(|e| ImageError::Io { path: path.to_path_buf(), source: e })?;
+        let hash: String = Sha256::digest(&bytes).iter().take(8).map(|b| format!("{:02x}", b)).collect();
+
+        let (width, height) = oriented_dimensions(&mut image_decoder(path, &bytes)?)
+            .map_err(|e| decode_error(path, e))?;
+
+        Ok(SourceImage {
+            path: path.to_path_buf(),
+            url_path: url_path.to_string(),
+            stem: path.file_stem().unwrap_or_default().to_string_lossy().to_string(),
+            bytes,
+            hash,
+            width,
+            height,
+            decoded: None,
+        })
+    }
+
+    fn decoded(&mut self) -> Result<&DynamicImage, ImageError> {
+        let image = match self.decoded.take() {
+            Some(image) => image,
+            None => {
+                let mut decoder = image_decoder(&self.path, &self.bytes)?;
+                let orientation = decoder.orientation().map_err(|e| decode_error(&self.path, e))?;
+                let mut image = DynamicImage::from_decoder(decoder).map_err(|e| decode_error(&self.path, e))?;
+                image.apply_orientation(orientation);
+                image
+            }
+        };
+        Ok(self.decoded.insert(image))
+    }
+}
+
+fn image_decoder<'a>(path: &Path, bytes: &'a [u8]) -> Result<impl ImageDecoder + 'a, ImageError> {
+    ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| ImageError::Io { path: path.to_path_buf(), source: e })?
+        .into_decoder()
+        .map_err(|e| decode_error(path, e))
+}
+
+// Header dimensions without decoding the pixels (GIF included), None for unreadable files
+fn header_dimensions(path: &Path) -> Option<(u32, u32)> {
+    let file = fs::File::open(path).ok()?;
+    let mut decoder = ImageReader::new(BufReader::new(file))
+        .with_guessed_format().ok()?
+        .into_decoder().ok()?;
+    oriented_dimensions(&mut decoder).ok()
+}
+
+// Width and height after EXIF orientation
+fn oriented_dimensions(decoder: &mut impl ImageDecoder) -> Result<(u32, u32), image::ImageError> {
+    let (width, height) = decoder.dimensions();
+    Ok(match decoder.orientation()? {
+        Orientation::Rotate90 | Orientation::Rotate270
+        | Orientation::Rotate90FlipH | Orientation::Rotate270FlipH => (height, width),
+        _ => (width, height),
+    })
+}
+
+fn decode_error(path: &Path, e: image::ImageError) -> ImageError {
+    ImageError::DecodeError { path: path.to_path_buf(), reason: e.to_string() }
+}
+
+fn encode(image: &DynamicImage, encoding: Encoding, quality: u8) -> Result<Vec<u8>, String> {
+    let mut data = Vec::new();
+    let has_alpha = image.color().has_alpha();
+
+    match encoding {
+        // JPEG has no alpha channel
+        Encoding::Jpeg => image.to_rgb8()
+            .write_with_encoder(JpegEncoder::new_with_quality(&mut data, quality))
+            .map_err(|e| e.to_string())?,
+        Encoding::Png => image
+            .write_with_encoder(PngEncoder::new_with_quality(&mut data, CompressionType::Best, PngFilterType::Adaptive))
+            .map_err(|e| e.to_string())?,
+        Encoding::Avif if has_alpha => image.to_rgba8()
+            .write_with_encoder(AvifEncoder::new_with_speed_quality(&mut data, AVIF_SPEED, quality))
+            .map_err(|e| e.to_string())?,
+        Encoding::Avif => image.to_rgb8()
+            .write_with_encoder(AvifEncoder::new_with_speed_quality(&mut data, AVIF_SPEED, quality))
+            .map_err(|e| e.to_string())?,
+        // Lossy WebP (the image crate only writes lossless WebP)
+        Encoding::Webp => {
+            let encoded = if has_alpha {
+                let rgba = image.to_rgba8();
+                webp::Encoder::from_rgba(&rgba, rgba.width(), rgba.height()).encode_simple(false, quality as f32)
+            } else {
+                let rgb = image.to_rgb8();
+                webp::Encoder::from_rgb(&rgb, rgb.width(), rgb.height()).encode_simple(false, quality as f32)
+            };
+            data.extend_from_slice(&encoded.map_err(|e| format!("{:?}", e))?);
+        }
+    }
+    Ok(data)
+}
+
+//------------------------------------------------------------------------------
+// EXIF GPS stripping
+//------------------------------------------------------------------------------
+
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+const XMP_HEADER: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+const GPS_IFD_TAG: u16 = 0x8825;
+
+/// True for the files `strip_gps` applies to
+pub fn is_jpeg(path: &Path) -> bool {
+    Encoding::from_path(path) == Some(Encoding::Jpeg)
+}
+
+/// Removes the GPS location of a JPEG: the EXIF GPS directory is emptied (other EXIF data such as
+/// orientation is kept) and XMP packets mentioning GPS are dropped. Data that isn't a JPEG is
+/// returned unchanged; an unreadable EXIF block is dropped as a whole.
+pub fn strip_gps(data: &[u8]) -> Vec<u8> {
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return data.to_vec();
+    }
+
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(&data[..2]);
+    let mut pos = 2;
+
+    while pos + 4 <= data.len() && data[pos] == 0xFF {
+        let marker = data[pos + 1];
+        // Start of scan: the compressed image data runs to the end of the file
+        if marker == 0xDA {
+            break;
+        }
+        let length = u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+        let end = pos + 2 + length;
+        if length < 2 || end > data.len() {
+            break;
+        }
+
+        let payload = &data[pos + 4..end];
+        if marker == 0xE1 && payload.starts_with(EXIF_HEADER) {
+            let mut segment = data[pos..end].to_vec();
+            if strip_gps_ifd(&mut segment[4 + EXIF_HEADER.len()..]).is_some() {
+                out.extend_from_slice(&segment);
+            }
+        } else if !(marker == 0xE1 && payload.starts_with(XMP_HEADER) && contains(payload, b"exif:GPS")) {
+            out.extend_from_slice(&data[pos..end]);
+        }
+        pos = end;
+    }
+
+    out.extend_from_slice(&data[pos..]);
+    out
+}
+
+// Empties the GPS IFD of a TIFF structure in place (None when the structure is malformed)
+fn strip_gps_ifd(tiff: &mut [u8]) -> Option<()> {
+    let little_endian = match tiff.get(..2)? {
+        b"II" => true,
+        b"MM" => false,
+        _ => return None,
+    };
+    let read_u16 = |tiff: &[u8], at: usize| -> Option<u16> {
+        let bytes = [*tiff.get(at)?, *tiff.get(at + 1)?];
+        Some(if little_endian { u16::from_le_bytes(bytes) } else { u16::from_be_bytes(bytes) })
+    };
+    let read_u32 = |tiff: &[u8], at: usize| -> Option<usize> {
+        let bytes: [u8; 4] = tiff.get(at..at + 4)?.try_into().ok()?;
+        Some(if little_endian { u32::from_le_bytes(bytes) } else { u32::from_be_bytes(bytes) } as usize)
+    };
+
+    // IFD0: find the GPS IFD pointer
+    let ifd0 = read_u32(tiff, 4)?;
+    let entries = read_u16(tiff, ifd0)? as usize;
+    let Some(gps_ifd) = (0..entries)
+        .map(|i| ifd0 + 2 + i * 12)
+        .find(|entry| read_u16(tiff, *entry) == Some(GPS_IFD_TAG))
+    else {
+        return Some(());    // No GPS data
+    };
+    let gps_ifd = read_u32(tiff, gps_ifd + 8)?;
+    let gps_entries = read_u16(tiff, gps_ifd)? as usize;
+    tiff.get(gps_ifd..gps_ifd + 2 + gps_entries * 12 + 4)?;
+
+    // Values longer than 4 bytes are stored elsewhere in the block
+    for i in 0..gps_entries {
+        let entry = gps_ifd + 2 + i * 12;
+        let unit = match read_u16(tiff, entry + 2)? {
+            1 | 2 | 6 | 7 => 1,
+            3 | 8 => 2,
+            4 | 9 | 11 => 4,
+            5 | 10 | 12 => 8,
+            _ => continue,
+        };
+        let size = read_u32(tiff, entry + 4)?.checked_mul(unit)?;
+        if size > 4 {
+            let offset = read_u32(tiff, entry + 8)?;
+            if let Some(value) = tiff.get_mut(offset..offset.checked_add(size)?) {
+                value.fill(0);
+            }
+        }
+    }
+
+    // Zero entry count, entries and next IFD offset
+    tiff[gps_ifd..gps_ifd + 2 + gps_entries * 12 + 4].fill(0);
+    Some(())
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|window| window == needle)
+}
+
+//------------------------------------------------------------------------------
+// Template function
+//------------------------------------------------------------------------------
+
+/// Registers `resize_image(path, width, format=none)`: URL of a resized copy of a content image
+/// (`path` is a site URL such as "/images/photo.jpg" or `post.thumbnail`).
+/// Returns `path` unchanged when image processing is disabled.
+pub fn register_all(env: &mut Environment, pipeline: Option<Arc<ImagePipeline>>) {
+    env.add_function("resize_image", move |path: Value, width: u32, kwargs: Kwargs| -> Result<Value, Error> {
+        let path = path.as_str().ok_or_else(|| {
+            Error::new(ErrorKind::InvalidOperation, "resize_image: expected a string path")
+        })?;
+        let format = match kwargs.get::<Option<String>>("format")?.as_deref() {
+            None => None,
+            Some("avif") => Some(ImageFormat::Avif),
+            Some("webp") => Some(ImageFormat::Webp),
+            Some(other) => return Err(Error::new(ErrorKind::InvalidOperation,
+                format!("resize_image: unknown format '{}' (expected avif or webp)", other))),
+        };
+        kwargs.assert_all_used()?;
+
+        let Some(pipeline) = &pipeline else {
+            return Ok(url_helpers::url_value(path.to_string()));
+        };
+        if width == 0 {
+            return Err(Error::new(ErrorKind::InvalidOperation, "resize_image: width must be greater than 0"));
+        }
+
+        pipeline.resize(path, width, format)
+            .map(url_helpers::url_value)
+            .map_err(|e| Error::new(ErrorKind::InvalidOperation, format!("resize_image: {}", e)))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // JPEG fixture: SOI, the given APPn segments, a scan and EOI (not decodable, but structurally valid)
+    fn jpeg(segments: &[(u8, Vec<u8>)]) -> Vec<u8> {
+        let mut data = vec![0xFF, 0xD8];
+        for (marker, payload) in segments {
+            data.extend_from_slice(&[0xFF, *marker]);
+            data.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+            data.extend_from_slice(payload);
+        }
+        data.extend_from_slice(&[0xFF, 0xDA, 0x00, 0x04, 0x01, 0x02, 0x12, 0x34, 0xFF, 0xD9]);
+        data
+    }
+
+    // TIFF block: IFD0 (Orientation, GPS pointer), GPS IFD (GPSLatitudeRef inline, GPSLatitude at an offset)
+    fn tiff(little_endian: bool) -> Vec<u8> {
+        let u16b = |v: u16| if little_endian { v.to_le_bytes().to_vec() } else { v.to_be_bytes().to_vec() };
+        let u32b = |v: u32| if little_endian { v.to_le_bytes().to_vec() } else { v.to_be_bytes().to_vec() };
+        let entry = |tag: u16, kind: u16, count: u32, value: Vec<u8>| [u16b(tag), u16b(kind), u32b(count), value].concat();
+
+        let mut data = if little_endian { b"II".to_vec() } else { b"MM".to_vec() };
+        data.extend(u16b(42));
+        data.extend(u32b(8));
+        // IFD0 at 8
+        data.extend(u16b(2));
+        data.extend(entry(0x0112, 3, 1, [u16b(6), vec![0, 0]].concat()));
+        data.extend(entry(GPS_IFD_TAG, 4, 1, u32b(38)));
+        data.extend(u32b(0));
+        // GPS IFD at 38
+        data.extend(u16b(2));
+        data.extend(entry(0x0001, 2, 2, b"N\0\0\0".to_vec()));
+        data.extend(entry(0x0002, 5, 3, u32b(68)));
+        data.extend(u32b(0));
+        // GPSLatitude rationals at 68
+        for v in [37, 1, 46, 1, 1234, 100] {
+            data.extend(u32b(v));
+        }
+        data
+    }
+
+    fn exif(tiff: Vec<u8>) -> (u8, Vec<u8>) {
+        (0xE1, [EXIF_HEADER.to_vec(), tiff].concat())
+    }
+
+    fn xmp(body: &str) -> (u8, Vec<u8>) {
+        (0xE1, [XMP_HEADER, body.as_bytes()].concat())
+    }
+
+    fn assert_gps_removed(little_endian: bool) {
+        let original = tiff(little_endian);
+        let stripped = strip_gps(&jpeg(&[exif(original.clone())]));
+        assert_eq!(stripped.len(), jpeg(&[exif(original.clone())]).len());
+
+        let block = &stripped[2 + 4 + EXIF_HEADER.len()..][..original.len()];    // After SOI and the APP1 header
+        // IFD0 (Orientation and the pointer itself) is untouched
+        assert_eq!(block[..38], original[..38]);
+        // GPS IFD (count, entries, next offset) and the out-of-line rationals are zeroed
+        assert!(block[38..].iter().all(|b| *b == 0));
+        assert!(!stripped.windows(2).any(|w| w == b"N\0"));
+        // Scan data is kept
+        assert!(stripped.ends_with(&[0xFF, 0xDA, 0x00, 0x04, 0x01, 0x02, 0x12, 0x34, 0xFF, 0xD9]));
+    }
+
+    #[test]
+    fn strip_gps_little_endian() {
+        assert_gps_removed(true);
+    }
+
+    #[test]
+    fn strip_gps_big_endian() {
+        assert_gps_removed(false);
+    }
+
+    #[test]
+    fn strip_gps_keeps_exif_without_gps() {
+        let mut original = tiff(false);
+        original[8 + 2 + 12..8 + 2 + 14].copy_from_slice(&0x0131u16.to_be_bytes());     // GPS pointer -> Software tag
+        let data = jpeg(&[exif(original)]);
+        assert_eq!(strip_gps(&data), data);
+    }
+
+    #[test]
+    fn strip_gps_drops_malformed_exif() {
+        let app0 = (0xE0, b"JFIF\0\x01\x01\0\0\x01\0\x01\0\0".to_vec());
+
+        let mut bad_byte_order = tiff(true);
+        bad_byte_order[..2].copy_from_slice(b"XX");
+        let mut bad_gps_offset = tiff(true);
+        bad_gps_offset[8 + 2 + 12 + 8..8 + 2 + 12 + 12].copy_from_slice(&0xFFFFu32.to_le_bytes());
+        let truncated = tiff(true)[..50].to_vec();
+
+        for block in [bad_byte_order, bad_gps_offset, truncated] {
+            let stripped = strip_gps(&jpeg(&[app0.clone(), exif(block)]));
+            assert_eq!(stripped, jpeg(std::slice::from_ref(&app0)));
+        }
+    }
+
+    #[test]
+    fn strip_gps_drops_xmp_with_gps() {
+        let with_gps = xmp(r#"<rdf:Description exif:GPSLatitude="37,46N"/>"#);
+        let without_gps = xmp(r#"<rdf:Description dc:title="Beach"/>"#);
+
+        let stripped = strip_gps(&jpeg(&[with_gps, without_gps.clone()]));
+        assert_eq!(stripped, jpeg(&[without_gps]));
+    }
+
+    #[test]
+    fn strip_gps_ignores_other_files() {
+        let png = b"\x89PNG\r\n\x1a\n0000".to_vec();
+        assert_eq!(strip_gps(&png), png);
+
+        let plain = jpeg(&[(0xE0, b"JFIF\0".to_vec())]);
+        assert_eq!(strip_gps(&plain), plain);
+    }
+}
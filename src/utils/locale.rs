@@ -0,0 +1,297 @@
+// src/utils/locale.rs
+
+//------------------------------------------------------------------------------
+// Localized date names
+//------------------------------------------------------------------------------
+
+/// Month/weekday names for a locale (weekdays start on Monday)
+pub struct DateNames {
+    pub months: [&'static str; 12],
+    pub months_short: [&'static str; 12],
+    pub weekdays: [&'static str; 7],
+    pub weekdays_short: [&'static str; 7],
+    pub am_pm: [&'static str; 2],
+}
+
+const EN: DateNames = DateNames {
+    months: ["January", "February", "March", "April", "May", "June",
+             "July", "August", "September", "October", "November", "December"],
+    months_short: ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"],
+    weekdays: ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"],
+    weekdays_short: ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+    am_pm: ["AM", "PM"],
+};
+
+const KO: DateNames = DateNames {
+    months: ["1월", "2월", "3월", "4월", "5월", "6월", "7월", "8월", "9월", "10월", "11월", "12월"],
+    months_short: ["1월", "2월", "3월", "4월", "5월", "6월", "7월", "8월", "9월", "10월", "11월", "12월"],
+    weekdays: ["월요일", "화요일", "수요일", "목요일", "금요일", "토요일", "일요일"],
+    weekdays_short: ["월", "화", "수", "목", "금", "토", "일"],
+    am_pm: ["오전", "오후"],
+};
+
+const JA: DateNames = DateNames {
+    months: ["1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月"],
+    months_short: ["1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月"],
+    weekdays: ["月曜日", "火曜日", "水曜日", "木曜日", "金曜日", "土曜日", "日曜日"],
+    weekdays_short: ["月", "火", "水", "木", "金", "土", "日"],
+    am_pm: ["午前", "午後"],
+};
+
+const DE: DateNames = DateNames {
+    months: ["Januar", "Februar", "März", "April", "Mai", "Juni",
+             "Juli", "August", "September", "Oktober", "November", "Dezember"],
+    months_short: ["Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.", "Okt.", "Nov.", "Dez."],
+    weekdays: ["Montag", "Dienstag", "Mittwoch", "Donnerstag", "Freitag", "Samstag", "Sonntag"],
+    weekdays_short: ["Mo.", "Di.", "Mi.", "Do.", "Fr.", "Sa.", "So."],
+    am_pm: ["AM", "PM"],
+};
+
+const FR: DateNames = DateNames {
+    months: ["janvier", "février", "mars", "avril", "mai", "juin",
+             "juillet", "août", "septembre", "octobre", "novembre", "décembre"],
+    months_short: ["janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.", "déc."],
+    weekdays: ["lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche"],
+    weekdays_short: ["lun.", "mar.", "mer.", "jeu.", "ven.", "sam.", "dim."],
+    am_pm: ["AM", "PM"],
+};
+
+// Primary language subtag (e.g., "ko-KR" -> "ko")
+fn primary_language(locale: &str) -> String {
+    locale.split(['-', '_']).next().unwrap_or(locale).to_lowercase()
+}
+
+/// Date names for a locale, falling back to English
+pub fn date_names(locale: &str) -> &'static DateNames {
+    match primary_language(locale).as_str() {
+        "ko" => &KO,
+        "ja" => &JA,
+        "de" => &DE,
+        "fr" => &FR,
+        _ => &EN,
+    }
+}
+
+/// Replaces the name specifiers of a chrono format string with localized names
+///   - %B / %b (%h): month name, %A / %a: weekday name, %p: AM/PM (only when `hour` is known)
+///   - `month0`: 0-based month, `weekday0`: 0-based weekday from Monday
+pub fn localize_format(fmt: &str, names: &DateNames, month0: usize, weekday0: usize, hour: Option<u32>) -> String {
+    let mut out = String::with_capacity(fmt.len());
+    let mut chars = fmt.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != '%' {
+            out.push(ch);
+            continue;
+        }
+
+        match chars.peek().copied() {
+            Some('B') => { chars.next(); out.push_str(names.months[month0]); }
+            Some('b') | Some('h') => { chars.next(); out.push_str(names.months_short[month0]); }
+            Some('A') => { chars.next(); out.push_str(names.weekdays[weekday0]); }
+            Some('a') => { chars.next(); out.push_str(names.weekdays_short[weekday0]); }
+            Some('p') if hour.is_some() => {
+                chars.next();
+                out.push_str(names.am_pm[if hour.unwrap_or(0) < 12 { 0 } else { 1 }]);
+            }
+            Some(next) => {
+                // Keep other specifiers (and "%%") for chrono
+                chars.next();
+                out.push('%');
+                out.push(next);
+            }
+            None => out.push('%'),
+        }
+    }
+
+    out
+}
+
+//------------------------------------------------------------------------------
+// Relative time ("3 days ago", "3일 전")
+//------------------------------------------------------------------------------
+
+#[derive(Debug, Copy, Clone)]
+enum TimeUnit {
+    Minute,
+    Hour,
+    Day,
+    Month,
+    Year,
+}
+
+/// Describes `seconds` relative to now (positive = past, negative = future)
+pub fn relative_time(seconds: i64, locale: &str) -> String {
+    let past = seconds >= 0;
+    let secs = seconds.unsigned_abs();
+
+    let (n, unit) = match secs {
+        s if s < 60 => return just_now(locale),
+        s if s < 3600 => (s / 60, TimeUnit::Minute),
+        s if s < 86_400 => (s / 3600, TimeUnit::Hour),
+        s if s < 30 * 86_400 => (s / 86_400, TimeUnit::Day),
+        s if s < 365 * 86_400 => (s / (30 * 86_400), TimeUnit::Month),
+        s => (s / (365 * 86_400), TimeUnit::Year),
+    };
+
+    match primary_language(locale).as_str() {
+        "ko" => {
+            let unit = match unit {
+                TimeUnit::Minute => "분",
+                TimeUnit::Hour => "시간",
+                TimeUnit::Day => "일",
+                TimeUnit::Month => "개월",
+                TimeUnit::Year => "년",
+            };
+            format!("{}{} {}", n, unit, if past { "전" } else { "후" })
+        }
+        "ja" => {
+            let unit = match unit {
+                TimeUnit::Minute => "分",
+                TimeUnit::Hour => "時間",
+                TimeUnit::Day => "日",
+                TimeUnit::Month => "か月",
+                TimeUnit::Year => "年",
+            };
+            format!("{}{}{}", n, unit, if past { "前" } else { "後" })
+        }
+        "de" => {
+            // Dative forms: "vor 3 Tagen", "in 3 Tagen"
+            let unit = match (unit, n == 1) {
+                (TimeUnit::Minute, true) => "Minute",
+                (TimeUnit::Minute, false) => "Minuten",
+                (TimeUnit::Hour, true) => "Stunde",
+                (TimeUnit::Hour, false) => "Stunden",
+                (TimeUnit::Day, true) => "Tag",
+                (TimeUnit::Day, false) => "Tagen",
+                (TimeUnit::Month, true) => "Monat",
+                (TimeUnit::Month, false) => "Monaten",
+                (TimeUnit::Year, true) => "Jahr",
+                (TimeUnit::Year, false) => "Jahren",
+            };
+            format!("{} {} {}", if past { "vor" } else { "in" }, n, unit)
+        }
+        "fr" => {
+            let unit = match (unit, n == 1) {
+                (TimeUnit::Minute, true) => "minute",
+                (TimeUnit::Minute, false) => "minutes",
+                (TimeUnit::Hour, true) => "heure",
+                (TimeUnit::Hour, false) => "heures",
+                (TimeUnit::Day, true) => "jour",
+                (TimeUnit::Day, false) => "jours",
+                (TimeUnit::Month, _) => "mois",
+                (TimeUnit::Year, true) => "an",
+                (TimeUnit::Year, false) => "ans",
+            };
+            format!("{} {} {}", if past { "il y a" } else { "dans" }, n, unit)
+        }
+        _ => {
+            let unit = match unit {
+                TimeUnit::Minute => "minute",
+                TimeUnit::Hour => "hour",
+                TimeUnit::Day => "day",
+                TimeUnit::Month => "month",
+                TimeUnit::Year => "year",
+            };
+            let plural = if n == 1 { "" } else { "s" };
+            if past {
+                format!("{} {}{} ago", n, unit, plural)
+            } else {
+                format!("in {} {}{}", n, unit, plural)
+            }
+        }
+    }
+}
+
+fn just_now(locale: &str) -> String {
+    match primary_language(locale).as_str() {
+        "ko" => "방금 전",
+        "ja" => "たった今",
+        "de" => "gerade eben",
+        "fr" => "à l'instant",
+        _ => "just now",
+    }.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-02-15 was a Thursday: month0 = 1, weekday0 = 3
+    fn format(fmt: &str, locale: &str, hour: Option<u32>) -> String {
+        localize_format(fmt, date_names(locale), 1, 3, hour)
+    }
+
+    #[test]
+    fn month_and_weekday_names() {
+        assert_eq!(format("%A, %d %B %Y", "en", None), "Thursday, %d February %Y");
+        assert_eq!(format("%Y년 %B %d일 (%a)", "ko", None), "%Y년 2월 %d일 (목)");
+        assert_eq!(format("%Y年%B%d日 %A", "ja", None), "%Y年2月%d日 木曜日");
+        assert_eq!(format("%A, %d. %B %Y", "de", None), "Donnerstag, %d. Februar %Y");
+        assert_eq!(format("%a %d %b %Y", "fr", None), "jeu. %d févr. %Y");
+    }
+
+    #[test]
+    fn locale_matching() {
+        assert_eq!(format("%B", "ko-KR", None), "2월");
+        assert_eq!(format("%B", "fr_CA", None), "février");
+        assert_eq!(format("%B", "pt", None), "February");     // Unknown -> English
+        assert_eq!(format("%h", "de", None), "Feb.");
+    }
+
+    #[test]
+    fn am_pm_and_other_specifiers() {
+        assert_eq!(format("%p %I:%M", "ko", Some(9)), "오전 %I:%M");
+        assert_eq!(format("%p", "ja", Some(12)), "午後");
+        assert_eq!(format("%p", "en", None), "%p");           // Plain dates: left to chrono
+        assert_eq!(format("100%% %B%", "en", None), "100%% February%");
+    }
+
+    #[test]
+    fn relative_time_boundaries() {
+        const MINUTE: i64 = 60;
+        const HOUR: i64 = 3600;
+        const DAY: i64 = 86_400;
+
+        let en = |seconds| relative_time(seconds, "en");
+        assert_eq!(en(0), "just now");
+        assert_eq!(en(59), "just now");
+        assert_eq!(en(MINUTE), "1 minute ago");
+        assert_eq!(en(HOUR - 1), "59 minutes ago");
+        assert_eq!(en(HOUR), "1 hour ago");
+        assert_eq!(en(DAY - 1), "23 hours ago");
+        assert_eq!(en(DAY), "1 day ago");
+        assert_eq!(en(30 * DAY - 1), "29 days ago");
+        assert_eq!(en(30 * DAY), "1 month ago");
+        assert_eq!(en(365 * DAY - 1), "12 months ago");
+        assert_eq!(en(365 * DAY), "1 year ago");
+        assert_eq!(en(3 * 365 * DAY), "3 years ago");
+
+        // Future
+        assert_eq!(en(-59), "just now");
+        assert_eq!(en(-MINUTE), "in 1 minute");
+        assert_eq!(en(-3 * DAY), "in 3 days");
+        assert_eq!(en(-2 * 365 * DAY), "in 2 years");
+    }
+
+    #[test]
+    fn relative_time_locales() {
+        const DAY: i64 = 86_400;
+
+        assert_eq!(relative_time(3 * DAY, "ko"), "3일 전");
+        assert_eq!(relative_time(-2 * 3600, "ko"), "2시간 후");
+        assert_eq!(relative_time(30, "ko"), "방금 전");
+
+        assert_eq!(relative_time(5 * 60, "ja"), "5分前");
+        assert_eq!(relative_time(-60 * DAY, "ja"), "2か月後");
+
+        assert_eq!(relative_time(DAY, "de"), "vor 1 Tag");
+        assert_eq!(relative_time(3 * DAY, "de"), "vor 3 Tagen");
+        assert_eq!(relative_time(-365 * DAY, "de"), "in 1 Jahr");
+
+        assert_eq!(relative_time(3600, "fr"), "il y a 1 heure");
+        assert_eq!(relative_time(60 * DAY, "fr"), "il y a 2 mois");
+        assert_eq!(relative_time(-2 * 365 * DAY, "fr"), "dans 2 ans");
+        assert_eq!(relative_time(0, "fr"), "à l'instant");
+    }
+}
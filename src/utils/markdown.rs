@@ -0,0 +1,43 @@
+// src/utils/markdown.rs
+
+use comrak::{markdown_to_html_with_plugins, Options, options::Plugins};
+use comrak::plugins::syntect::SyntectAdapter;
+
+/// Markdown renderer with the site's comrak options and syntect highlighting.
+///
+/// Shared by content rendering and the `markdown` template filter.
+pub struct MarkdownRenderer {
+    options: Options<'static>,
+    adapter: SyntectAdapter,
+}
+
+impl MarkdownRenderer {
+    pub fn new() -> Self {
+        let mut options = Options::default();
+
+        // Extension options
+        options.extension.strikethrough = true;
+        options.extension.table = true;
+        options.extension.autolink = true;
+        options.extension.tasklist = true;
+        options.extension.header_ids = Some(String::new());
+        options.extension.footnotes = true;
+
+        // Render options
+        options.render.hardbreaks = false;
+        options.render.github_pre_lang = true;
+        options.render.r#unsafe = true;
+
+        Self {
+            options,
+            adapter: SyntectAdapter::new(None),
+        }
+    }
+
+    pub fn render(&self, markdown: &str) -> String {
+        let mut plugins = Plugins::default();
+        plugins.render.codefence_syntax_highlighter = Some(&self.adapter);
+
+        markdown_to_html_with_plugins(markdown, &self.options, &plugins)
+    }
+}
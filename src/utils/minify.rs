@@ -0,0 +1,75 @@
+// src/utils/minify.rs
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use lightningcss::stylesheet::{MinifyOptions, ParserOptions, PrinterOptions, StyleSheet};
+
+use crate::utils::output_sink::OutputSink;
+
+/// HTML minification (inline `<style>` and `<script>` are minified too)
+pub fn html(html: &[u8]) -> Vec<u8> {
+    let mut cfg = minify_html::Cfg::spec_compliant();
+    cfg.minify_css = true;
+    cfg.minify_js = true;
+    minify_html::minify(html, &cfg)
+}
+
+/// CSS minification, returns the reason on parse errors
+pub fn css(css: &str) -> Result<String, String> {
+    let mut stylesheet = StyleSheet::parse(css, ParserOptions::default())
+        .map_err(|e| e.to_string())?;
+    stylesheet.minify(MinifyOptions::default())
+        .map_err(|e| e.to_string())?;
+
+    let printer = PrinterOptions { minify: true, ..PrinterOptions::default() };
+    stylesheet.to_css(printer)
+        .map(|result| result.code)
+        .map_err(|e| e.to_string())
+}
+
+/// JavaScript minification (classic scripts, or ES modules for `.mjs`), returns the reason on syntax errors
+pub fn js(js: &[u8], module: bool) -> Result<Vec<u8>, String> {
+    let mode = if module { minify_js::TopLevelMode::Module } else { minify_js::TopLevelMode::Global };
+    let session = minify_js::Session::new();
+    let mut output = Vec::new();
+    minify_js::minify(&session, mode, js, &mut output)
+        .map_err(|e| format!("{:?}", e))?;
+    Ok(output)
+}
+
+//------------------------------------------------------------------------------
+// MinifySink - minifies the generated HTML pages before handing them to another sink
+//------------------------------------------------------------------------------
+pub struct MinifySink<'a> {
+    inner: &'a mut dyn OutputSink,
+}
+
+impl<'a> MinifySink<'a> {
+    pub fn new(inner: &'a mut dyn OutputSink) -> Self {
+        MinifySink { inner }
+    }
+}
+
+impl OutputSink for MinifySink<'_> {
+    fn prepare(&mut self) -> io::Result<()> {
+        self.inner.prepare()
+    }
+
+    fn write(&mut self, rel_path: &str, data: &[u8]) -> io::Result<()> {
+        if rel_path.ends_with(".html") {
+            self.inner.write(rel_path, &html(data))
+        } else {
+            self.inner.write(rel_path, data)
+        }
+    }
+
+    // Copied files (assets, images, data) are never touched
+    fn copy(&mut self, src: &Path, rel_path: &str) -> io::Result<()> {
+        self.inner.copy(src, rel_path)
+    }
+
+    fn location(&self, rel_path: &str) -> PathBuf {
+        self.inner.location(rel_path)
+    }
+}
@@ -1,4 +1,15 @@
 // src/utils/mod.rs
 
+pub mod assets;
+pub mod csp;
 pub mod filters;
+pub mod functions;
+pub mod html;
+pub mod images;
+pub mod locale;
+pub mod markdown;
+pub mod minify;
 pub mod output;
+pub mod output_sink;
+pub mod text;
+pub mod url_helpers;
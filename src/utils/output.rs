@@ -62,6 +62,11 @@ pub fn print_check(msg: &str) {
     eprintln!("  {}✓{} {}", GREEN, RESET, msg);
 }
 
+// Cross mark for failed items (red)
+pub fn print_cross(msg: &str) {
+    eprintln!("  {}✗{} {}", RED, RESET, msg);
+}
+
 // File item display (gray bullet point)
 pub fn print_file(msg: &str) {
     eprintln!("    {}·{} {}", GRAY, RESET, msg);
@@ -85,6 +90,7 @@ Commands:
   new <title>     Create a new blog post with the given title
   build           Build the blog into static files (for deployment)
   server          Start a local HTTP server to preview the blog
+  theme <cmd>     Manage themes (new <name>, list, check [name], install <path>)
   help            Show this help message
 
 Options:
@@ -100,7 +106,22 @@ Options:
     --root <path>     Blog project root directory (default: current directory)
 
   server:
-    --port <port>     Port for the local server (default: 8000)
-    --root <path>     Blog project root directory (default: current directory)
+    --host <addr>       Address to bind (default: 127.0.0.1, use 0.0.0.0 for LAN)
+    --port <port>       Port for the local server (default: 8000)
+    --workers <n>       Number of worker threads (default: 4)
+    --access-log <path> Append requests to an access log file
+    --memory            Render the site into memory instead of serving the output directory
+    --drafts            Include drafts in the in-memory build (requires --memory)
+    --root <path>       Blog project root directory (default: current directory)
+
+  theme:
+    new <name>          Create a new theme in themes/<name> from the default theme
+    list                List installed themes
+    check [name]        Check templates of a theme (default: theme.name of blogconfig.yaml)
+    install <path>      Install a theme from a directory, .zip, .tar.gz or .tar archive
+      --name <name>       Folder name under themes/ (default: archive or directory name)
+      --force             Overwrite an installed theme with the same name
+      --use               Switch theme.name in blogconfig.yaml to the installed theme
+    --root <path>       Blog project root directory (default: current directory)
 "#);
 }
\ No newline at end of file
@@ -0,0 +1,114 @@
+// src/utils/output_sink.rs
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::utils::output;
+
+/// Destination of the files generated by the build pipeline.
+///
+/// Paths are site-relative with `/` separators (e.g., `posts/hello/index.html`).
+pub trait OutputSink {
+    /// Called once before anything is written
+    fn prepare(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Write a generated file
+    fn write(&mut self, rel_path: &str, data: &[u8]) -> io::Result<()>;
+
+    /// Copy a source file (theme assets, images, data files) into the site
+    fn copy(&mut self, src: &Path, rel_path: &str) -> io::Result<()> {
+        let data = fs::read(src)?;
+        self.write(rel_path, &data)
+    }
+
+    /// Path used in error messages for a site-relative path
+    fn location(&self, rel_path: &str) -> PathBuf;
+}
+
+//------------------------------------------------------------------------------
+// DiskSink - writes into the output directory (build command)
+//------------------------------------------------------------------------------
+pub struct DiskSink {
+    output_dir: PathBuf,
+    clean: bool,
+}
+
+impl DiskSink {
+    pub fn new(output_dir: &Path, clean: bool) -> Self {
+        DiskSink { output_dir: output_dir.to_path_buf(), clean }
+    }
+}
+
+impl OutputSink for DiskSink {
+    fn prepare(&mut self) -> io::Result<()> {
+        // Clean output directory if needed (only if it looks like a previous build)
+        if self.clean && self.output_dir.is_dir() && self.output_dir.join("index.html").is_file() {
+            output::step("Cleaning output directory...");
+            fs::remove_dir_all(&self.output_dir)?;
+        }
+
+        // Create output directory if it doesn't exist
+        if !self.output_dir.is_dir() {
+            output::step("Creating output directory...");
+            fs::create_dir_all(&self.output_dir)?;
+        }
+        output::info("Output directory:");
+        output::print_path(&self.output_dir.display().to_string());
+
+        Ok(())
+    }
+
+    fn write(&mut self, rel_path: &str, data: &[u8]) -> io::Result<()> {
+        let file = self.location(rel_path);
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&file, data)
+    }
+
+    fn copy(&mut self, src: &Path, rel_path: &str) -> io::Result<()> {
+        let file = self.location(rel_path);
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(src, &file).map(|_| ())
+    }
+
+    fn location(&self, rel_path: &str) -> PathBuf {
+        self.output_dir.join(rel_path.trim_start_matches('/'))
+    }
+}
+
+//------------------------------------------------------------------------------
+// MemorySink - keeps the whole site in memory (server --memory)
+//------------------------------------------------------------------------------
+#[derive(Default)]
+pub struct MemorySink {
+    files: BTreeMap<String, Vec<u8>>,
+}
+
+impl MemorySink {
+    pub fn new() -> Self {
+        MemorySink::default()
+    }
+
+    /// Consume the sink, returning the site-relative path -> bytes map
+    pub fn into_files(self) -> BTreeMap<String, Vec<u8>> {
+        self.files
+    }
+}
+
+impl OutputSink for MemorySink {
+    fn write(&mut self, rel_path: &str, data: &[u8]) -> io::Result<()> {
+        self.files.insert(rel_path.trim_start_matches('/').to_string(), data.to_vec());
+        Ok(())
+    }
+
+    fn location(&self, rel_path: &str) -> PathBuf {
+        PathBuf::from(format!("memory:/{}", rel_path.trim_start_matches('/')))
+    }
+}
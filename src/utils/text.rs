@@ -0,0 +1,313 @@
+// src/utils/text.rs
+
+//------------------------------------------------------------------------------
+// Escaping
+//------------------------------------------------------------------------------
+
+/// Escapes XML special characters
+pub fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+     .replace('<', "&lt;")
+     .replace('>', "&gt;")
+     .replace('"', "&quot;")
+     .replace('\'', "&apos;")
+}
+
+//------------------------------------------------------------------------------
+// Word count (CJK-aware)
+//------------------------------------------------------------------------------
+
+// Chinese/Japanese characters are read one by one (no spaces between words).
+// Hangul is excluded: Korean separates words with spaces.
+fn is_cjk(ch: char) -> bool {
+    matches!(ch as u32,
+        0x3040..=0x30FF     // Hiragana, Katakana
+        | 0x3400..=0x4DBF   // CJK Extension A
+        | 0x4E00..=0x9FFF   // CJK Unified Ideographs
+        | 0xF900..=0xFAFF   // CJK Compatibility Ideographs
+        | 0xFF66..=0xFF9F   // Halfwidth Katakana
+        | 0x20000..=0x2FA1F // CJK Extension B and later
+    )
+}
+
+/// Counts of a text: space-separated words and CJK characters
+#[derive(Debug, Default, Copy, Clone)]
+pub struct WordCount {
+    pub words: usize,
+    pub cjk_chars: usize,
+}
+
+impl WordCount {
+    /// Words, counting each CJK character as one word
+    pub fn total(&self) -> usize {
+        self.words + self.cjk_chars
+    }
+}
+
+pub fn count_words(text: &str) -> WordCount {
+    let mut count = WordCount::default();
+    let mut in_word = false;
+
+    for ch in text.chars() {
+        if is_cjk(ch) {
+            count.cjk_chars += 1;
+            in_word = false;
+        } else if ch.is_alphanumeric() {
+            if !in_word {
+                count.words += 1;
+                in_word = true;
+            }
+        } else if ch.is_whitespace() {
+            in_word = false;
+        }
+        // Other punctuation (e.g., "don't", "e-mail") doesn't split words
+    }
+
+    count
+}
+
+//------------------------------------------------------------------------------
+// HTML
+//------------------------------------------------------------------------------
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input",
+    "link", "meta", "source", "track", "wbr",
+];
+
+enum HtmlToken<'a> {
+    Text(&'a str),
+    Entity(&'a str),            // "&amp;" (one visible character)
+    OpenTag(&'a str, String),   // raw tag, lowercase name
+    CloseTag(&'a str, String),
+    Other(&'a str),             // comments, doctype, self-closing and void tags
+}
+
+// Minimal tokenizer for generated HTML (markdown output, summaries)
+fn tokenize_html(html: &str) -> Vec<HtmlToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if rest.starts_with("<!--") {
+            let end = rest.find("-->").map(|i| i + 3).unwrap_or(rest.len());
+            tokens.push(HtmlToken::Other(&rest[..end]));
+            rest = &rest[end..];
+        } else if rest.starts_with('<') && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/' || c == '!') {
+            let end = rest.find('>').map(|i| i + 1).unwrap_or(rest.len());
+            let raw = &rest[..end];
+            let inner = raw.trim_start_matches('<').trim_end_matches('>');
+            let closing = inner.starts_with('/');
+            let name: String = inner.trim_start_matches('/')
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric())
+                .collect::<String>()
+                .to_ascii_lowercase();
+
+            let token = if inner.starts_with('!') || inner.ends_with('/') || VOID_ELEMENTS.contains(&name.as_str()) {
+                HtmlToken::Other(raw)
+            } else if closing {
+                HtmlToken::CloseTag(raw, name)
+            } else {
+                HtmlToken::OpenTag(raw, name)
+            };
+            tokens.push(token);
+            rest = &rest[end..];
+        } else if rest.starts_with('&') {
+            let end = rest.find(';')
+                .filter(|&i| i <= 10 && !rest[1..i].contains(char::is_whitespace))
+                .map(|i| i + 1)
+                .unwrap_or(1);
+            tokens.push(if end > 1 { HtmlToken::Entity(&rest[..end]) } else { HtmlToken::Text(&rest[..1]) });
+            rest = &rest[end..];
+        } else {
+            let end = rest.char_indices()
+                .skip(1)
+                .find(|(_, c)| *c == '<' || *c == '&')
+                .map(|(i, _)| i)
+                .unwrap_or(rest.len());
+            tokens.push(HtmlToken::Text(&rest[..end]));
+            rest = &rest[end..];
+        }
+    }
+
+    tokens
+}
+
+fn decode_entity(entity: &str) -> String {
+    match entity {
+        "&amp;" => "&".to_string(),
+        "&lt;" => "<".to_string(),
+        "&gt;" => ">".to_string(),
+        "&quot;" => "\"".to_string(),
+        "&apos;" | "&#39;" => "'".to_string(),
+        "&nbsp;" => " ".to_string(),
+        _ => {
+            let code = entity.trim_start_matches("&#").trim_end_matches(';');
+            let parsed = match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => code.parse::<u32>().ok(),
+            };
+            parsed.filter(|_| entity.starts_with("&#"))
+                .and_then(char::from_u32)
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| entity.to_string())
+        }
+    }
+}
+
+/// Removes tags (and `<script>`/`<style>` contents), decodes entities and collapses whitespace
+pub fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut skip_until: Option<String> = None;
+
+    for token in tokenize_html(html) {
+        match token {
+            HtmlToken::OpenTag(_, name) if name == "script" || name == "style" => skip_until = Some(name),
+            HtmlToken::CloseTag(_, name) if skip_until.as_deref() == Some(name.as_str()) => skip_until = None,
+            _ if skip_until.is_some() => {}
+            HtmlToken::Text(s) => text.push_str(s),
+            HtmlToken::Entity(e) => text.push_str(&decode_entity(e)),
+            // Tags separate words ("<p>a</p><p>b</p>" -> "a b")
+            _ => text.push(' '),
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Truncates HTML to `length` visible characters, closing the tags left open.
+///
+/// `end` is appended when truncated.
+pub fn truncate_html(html: &str, length: usize, end: &str) -> String {
+    let tokens = tokenize_html(html);
+    let is_visible = |token: &HtmlToken| match token {
+        HtmlToken::Text(s) => !s.trim().is_empty(),
+        HtmlToken::Entity(_) => true,
+        _ => false,
+    };
+
+    let mut out = String::with_capacity(html.len().min(length * 2));
+    let mut open_tags: Vec<&str> = Vec::new();
+    let mut visible = 0;
+    let mut truncated = false;
+
+    for (i, token) in tokens.iter().enumerate() {
+        // Stop only if visible content remains (trailing tags and whitespace are kept)
+        if visible >= length && tokens[i..].iter().any(is_visible) {
+            truncated = true;
+            break;
+        }
+
+        match token {
+            HtmlToken::Text(s) => {
+                let count = s.chars().count();
+                if visible + count <= length || s.trim().is_empty() {
+                    out.push_str(s);
+                    visible += count;
+                } else {
+                    out.extend(s.chars().take(length - visible));
+                    truncated = true;
+                    break;
+                }
+            }
+            HtmlToken::Entity(e) => {
+                out.push_str(e);
+                visible += 1;
+            }
+            HtmlToken::OpenTag(raw, name) => {
+                out.push_str(raw);
+                open_tags.push(name);
+            }
+            HtmlToken::CloseTag(raw, name) => {
+                // Close up to the matching tag (ignores stray closing tags)
+                if let Some(pos) = open_tags.iter().rposition(|t| t == name) {
+                    for tag in open_tags[pos + 1..].iter().rev() {
+                        out.push_str(&format!("</{}>", tag));
+                    }
+                    out.push_str(raw);
+                    open_tags.truncate(pos);
+                }
+            }
+            HtmlToken::Other(raw) => out.push_str(raw),
+        }
+    }
+
+    if truncated {
+        let trimmed_len = out.trim_end().len();
+        out.truncate(trimmed_len);
+        out.push_str(end);
+    }
+    for tag in open_tags.iter().rev() {
+        out.push_str(&format!("</{}>", tag));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_counts_entities_as_one_character() {
+        assert_eq!(truncate_html("<p>a &amp; b</p>", 3, "…"), "<p>a &amp;…</p>");
+        assert_eq!(truncate_html("<p>&lt;tag&gt;</p>", 2, "…"), "<p>&lt;t…</p>");
+    }
+
+    #[test]
+    fn truncate_skips_void_and_self_closing_tags() {
+        assert_eq!(truncate_html("<p>ab<br>cd<img src=\"x\"/>ef</p>", 4, "…"), "<p>ab<br>cd…</p>");
+        assert_eq!(truncate_html("<p>ab<hr/>cd</p>", 10, "…"), "<p>ab<hr/>cd</p>");
+    }
+
+    #[test]
+    fn truncate_closes_open_tags() {
+        assert_eq!(
+            truncate_html("<div><p><em>hello world</em> more</p></div>", 5, "…"),
+            "<div><p><em>hello…</em></p></div>"
+        );
+        // Mis-nested closing tags close the inner tags first
+        assert_eq!(truncate_html("<b><i>x</b>y", 10, "…"), "<b><i>x</i></b>y");
+    }
+
+    #[test]
+    fn truncate_ignores_stray_closing_tags() {
+        assert_eq!(truncate_html("a</b>c</p>", 10, "…"), "ac");
+        assert_eq!(truncate_html("<p>a</span>bcd</p>", 2, "…"), "<p>ab…</p>");
+    }
+
+    #[test]
+    fn truncate_keeps_trailing_markup() {
+        // Nothing visible after the limit: not truncated
+        assert_eq!(truncate_html("<p>abc</p>\n", 3, "…"), "<p>abc</p>\n");
+        assert_eq!(truncate_html("<p>abc</p><p>d</p>", 3, "…"), "<p>abc…</p>");
+    }
+
+    #[test]
+    fn strip_tags_decodes_and_skips() {
+        assert_eq!(
+            strip_tags("<p>Tom &amp; Jerry</p><p>&#x41;&#66; &nbsp;x</p><script>var a = 1;</script><style>p{}</style>end"),
+            "Tom & Jerry AB x end"
+        );
+        assert_eq!(strip_tags("a<br/>b<!-- c -->d"), "a b d");
+        assert_eq!(strip_tags("a & b &foo; &#xZZ;"), "a & b &foo; &#xZZ;");
+    }
+
+    #[test]
+    fn word_counts() {
+        let count = count_words("Hello 世界 and 안녕하세요 세계");
+        assert_eq!((count.words, count.cjk_chars, count.total()), (4, 2, 6));
+
+        let count = count_words("日本語のテキスト");
+        assert_eq!((count.words, count.cjk_chars), (0, 8));
+
+        // CJK characters split Latin words
+        let count = count_words("ABC漢字DEF");
+        assert_eq!((count.words, count.cjk_chars), (2, 2));
+
+        assert_eq!(count_words("don't e-mail 3.14").words, 3);
+        assert_eq!(count_words(&strip_tags("<p>A&amp;B</p>")).total(), 1);
+        assert_eq!(count_words("").total(), 0);
+    }
+}
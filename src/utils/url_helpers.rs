@@ -0,0 +1,210 @@
+// src/utils/url_helpers.rs
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use minijinja::{Environment, Value, Error, ErrorKind};
+use minijinja::value::Kwargs;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use sha2::{Digest, Sha256, Sha384};
+
+use crate::config::blogconfig::BlogConfig;
+use crate::utils::assets::ThemeAsset;
+
+//------------------------------------------------------------------------------
+// URL joining
+//------------------------------------------------------------------------------
+
+/// Site URL settings (`site.base_url`, `site.path`)
+#[derive(Debug, Clone)]
+pub struct SiteUrls {
+    origin: String,     // base_url without the sub path (e.g., "https://example.com")
+    path: String,       // sub path: "" or "/blog"
+}
+
+impl SiteUrls {
+    pub fn new(base_url: &str, path: &str) -> Self {
+        let path = path.trim().trim_matches('/');
+        let path = if path.is_empty() { String::new() } else { format!("/{}", path) };
+
+        // base_url may or may not include the sub path ("https://example.com/blog" or "https://example.com")
+        let base_url = base_url.trim().trim_end_matches('/');
+        let origin = base_url.strip_suffix(path.as_str())
+            .filter(|_| !path.is_empty())
+            .unwrap_or(base_url)
+            .to_string();
+
+        Self { origin, path }
+    }
+
+    /// Root-relative URL with the sub path (e.g., "/posts/hello/" -> "/blog/posts/hello/")
+    pub fn relative(&self, url: &str) -> String {
+        if is_external(url) {
+            return url.to_string();
+        }
+
+        let url = format!("/{}", url.trim().trim_start_matches('/'));
+        let has_path = !self.path.is_empty()
+            && (url == self.path || url.starts_with(&format!("{}/", self.path)));
+        if has_path || self.path.is_empty() {
+            url
+        } else {
+            format!("{}{}", self.path, url)
+        }
+    }
+
+    /// Absolute URL (e.g., "/posts/hello/" -> "https://example.com/blog/posts/hello/")
+    pub fn absolute(&self, url: &str) -> String {
+        if is_external(url) {
+            return url.to_string();
+        }
+        format!("{}{}", self.origin, self.relative(url))
+    }
+}
+
+// Already absolute or not a path (left as-is)
+fn is_external(url: &str) -> bool {
+    url.contains("://")
+        || url.starts_with("//")
+        || url.starts_with('#')
+        || ["mailto:", "tel:", "data:"].iter().any(|scheme| url.starts_with(scheme))
+}
+
+//------------------------------------------------------------------------------
+// Asset fingerprints
+//------------------------------------------------------------------------------
+
+// Cache-busted path of every theme asset, relative to assets/:
+//   - fingerprinted files: "css/style.css" -> "css/style.1a2b3c4d.css"
+//   - others: "img/logo.png" -> "img/logo.png?v=1a2b3c4d" (compiled CSS for Sass sources)
+fn asset_paths(assets: &[ThemeAsset]) -> BTreeMap<String, String> {
+    assets.iter()
+        .filter_map(|asset| {
+            let output_path = match &asset.fingerprint {
+                Some(_) => asset.output_path(),
+                None => format!("{}?v={}", asset.path, short_hash(&asset.bytes().ok()?)),
+            };
+            Some((asset.path.clone(), output_path))
+        })
+        .collect()
+}
+
+// Subresource Integrity of the theme stylesheets and scripts ("css/style.css" -> "sha384-<base64>")
+fn asset_integrities(assets: &[ThemeAsset]) -> BTreeMap<String, String> {
+    assets.iter()
+        .filter(|asset| {
+            let ext = asset.path.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase());
+            matches!(ext.as_deref(), Some("css" | "js" | "mjs"))
+        })
+        .filter_map(|asset| {
+            let bytes = asset.bytes().ok()?;
+            Some((asset.path.clone(), format!("sha384-{}", BASE64.encode(Sha384::digest(&bytes)))))
+        })
+        .collect()
+}
+
+/// First 8 hex digits of the SHA-256 of `bytes`
+pub fn short_hash(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().take(4).map(|b| format!("{:02x}", b)).collect()
+}
+
+//------------------------------------------------------------------------------
+// Template functions & filters
+//------------------------------------------------------------------------------
+
+struct UrlHelpers {
+    urls: SiteUrls,
+    asset_paths: BTreeMap<String, String>,
+    asset_integrities: BTreeMap<String, String>,
+}
+
+impl UrlHelpers {
+    /// "/assets/<path>?v=<hash>" or the fingerprinted file (unchanged if the file doesn't exist)
+    fn asset_url(&self, path: &str) -> String {
+        let path = path.trim().trim_start_matches('/');
+        let path = path.strip_prefix("assets/").unwrap_or(path);
+
+        let path = self.asset_paths.get(path).map(String::as_str).unwrap_or(path);
+        self.urls.relative(&format!("/assets/{}", path))
+    }
+
+    /// "sha384-<base64>" of a theme stylesheet or script (an error for other paths, to catch typos)
+    fn asset_integrity(&self, path: &str) -> Result<String, Error> {
+        let path = path.trim().trim_start_matches('/');
+        let path = path.strip_prefix("assets/").unwrap_or(path);
+
+        self.asset_integrities.get(path).cloned().ok_or_else(|| {
+            Error::new(ErrorKind::InvalidOperation, format!("asset_integrity: '{}' is not a theme CSS/JS asset", path))
+        })
+    }
+}
+
+// URLs are returned as safe strings so autoescape doesn't turn "/" into "&#x2f;" (only markup characters are escaped)
+pub(crate) fn url_value(url: String) -> Value {
+    Value::from_safe_string(url
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;"))
+}
+
+fn expect_str<'a>(name: &str, value: &'a Value) -> Result<&'a str, Error> {
+    value.as_str().ok_or_else(|| {
+        Error::new(ErrorKind::InvalidOperation, format!("{}: expected a string value", name))
+    })
+}
+
+/// Registers the URL helpers:
+///   - `url_for(path, absolute=false)`: URL under `site.path` (or absolute with `site.base_url`)
+///   - `{{ url | relative_url }}`, `{{ url | absolute_url }}`
+///   - `asset_url(path)` / `{{ path | asset_url }}`: theme asset URL with a `?v=<hash>` fingerprint
+///     (or the fingerprinted file name with `build.fingerprint`)
+///   - `asset_integrity(path)` / `{{ path | asset_integrity }}`: SRI hash of a theme CSS/JS file
+pub fn register_all(env: &mut Environment, blog_config: &BlogConfig, assets: &[ThemeAsset]) {
+    let helpers = Arc::new(UrlHelpers {
+        urls: SiteUrls::new(&blog_config.site.base_url, &blog_config.site.path),
+        asset_paths: asset_paths(assets),
+        asset_integrities: asset_integrities(assets),
+    });
+
+    let h = Arc::clone(&helpers);
+    env.add_function("url_for", move |path: Value, kwargs: Kwargs| -> Result<Value, Error> {
+        let path = expect_str("url_for", &path)?;
+        let absolute = kwargs.get::<Option<bool>>("absolute")?.unwrap_or(false);
+        kwargs.assert_all_used()?;
+
+        Ok(url_value(if absolute { h.urls.absolute(path) } else { h.urls.relative(path) }))
+    });
+
+    let h = Arc::clone(&helpers);
+    env.add_filter("relative_url", move |url: Value| -> Result<Value, Error> {
+        Ok(url_value(h.urls.relative(expect_str("relative_url", &url)?)))
+    });
+
+    let h = Arc::clone(&helpers);
+    env.add_filter("absolute_url", move |url: Value| -> Result<Value, Error> {
+        Ok(url_value(h.urls.absolute(expect_str("absolute_url", &url)?)))
+    });
+
+    let h = Arc::clone(&helpers);
+    env.add_function("asset_url", move |path: Value| -> Result<Value, Error> {
+        Ok(url_value(h.asset_url(expect_str("asset_url", &path)?)))
+    });
+
+    let h = Arc::clone(&helpers);
+    env.add_filter("asset_url", move |path: Value| -> Result<Value, Error> {
+        Ok(url_value(h.asset_url(expect_str("asset_url", &path)?)))
+    });
+
+    let h = Arc::clone(&helpers);
+    env.add_function("asset_integrity", move |path: Value| -> Result<Value, Error> {
+        Ok(Value::from(h.asset_integrity(expect_str("asset_integrity", &path)?)?))
+    });
+
+    let h = Arc::clone(&helpers);
+    env.add_filter("asset_integrity", move |path: Value| -> Result<Value, Error> {
+        Ok(Value::from(h.asset_integrity(expect_str("asset_integrity", &path)?)?))
+    });
+}